@@ -0,0 +1,178 @@
+use chrono::prelude::*;
+use chrono::{Duration, Months};
+use std::ops::RangeInclusive;
+
+use crate::history::Attempt;
+use crate::session::Mode;
+use crate::{is_leap_year, random_bool, random_range, Method, MAX_YEAR, MIN_YEAR, YEARS};
+
+pub const MAX_LEVEL: u32 = 10;
+//the level changes after this many games
+const WINDOW: usize = 5;
+
+//the level of the day game follows the last games: found at the first guess and how fast
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Adaptive {
+    level: u32,
+    recent: Vec<(bool, f64)>,
+}
+
+impl Adaptive {
+    //the level is replayed from the day games of the history
+    pub fn from_history(attempts: &[Attempt]) -> Adaptive {
+        let mut adaptive = Adaptive::default();
+        for attempt in attempts.iter().filter(|attempt| attempt.mode == Mode::Day) {
+            adaptive.record(attempt.found_at_first_guess(), attempt.time);
+        }
+        adaptive
+    }
+
+    pub fn level(&self) -> u32 {
+        self.level
+    }
+
+    //up after 4 games out of 5 found at once in 20s, down after 3 missed or 60s
+    pub fn record(&mut self, found_at_first_guess: bool, time: f64) {
+        self.recent.push((found_at_first_guess, time));
+        if self.recent.len() < WINDOW {
+            return;
+        }
+        let found = self.recent.iter().filter(|(found, _)| *found).count();
+        let mut times: Vec<f64> = self.recent.iter().map(|(_, time)| *time).collect();
+        times.sort_by(f64::total_cmp);
+        let median_time = times[WINDOW / 2];
+        if found >= 4 && median_time <= 20.0 && self.level < MAX_LEVEL {
+            self.level += 1;
+            self.recent.clear();
+        } else if (found <= 2 || median_time > 60.0) && self.level > 0 {
+            self.level -= 1;
+            self.recent.clear();
+        } else {
+            self.recent.remove(0);
+        }
+    }
+
+    //from the years around today to every year the method supports
+    pub fn years(&self, method: Method) -> RangeInclusive<u32> {
+        let supported = method.supported_years();
+        let first_year = (1950 - 37 * self.level)
+            .max(*supported.start())
+            .max(MIN_YEAR);
+        let last_year = (2030 + 18 * self.level)
+            .min(*supported.end())
+            .min(MAX_YEAR - 1);
+        first_year..=last_year
+    }
+
+    //January and February of a leap year need one day less
+    pub fn leap_rate(&self) -> f64 {
+        0.03 * self.level as f64
+    }
+
+    //the years without a direct year table entry need the nearest leap year
+    pub fn off_table_rate(&self) -> f64 {
+        0.25 + 0.05 * self.level as f64
+    }
+
+    pub fn random_date(&self, method: Method) -> NaiveDate {
+        let years: Vec<i32> = self.years(method).map(|year| year as i32).collect();
+        let pick = |years: Vec<i32>| years[random_range(0..years.len())];
+        let leap_years: Vec<i32> = years.iter().copied().filter(|&y| is_leap_year(y)).collect();
+        if !leap_years.is_empty() && random_bool(self.leap_rate()) {
+            return random_day(pick(leap_years), 1..=2);
+        }
+        let off_table = random_bool(self.off_table_rate());
+        let chosen: Vec<i32> = years
+            .iter()
+            .copied()
+            .filter(|year| YEARS.contains_key(year) != off_table)
+            .collect();
+        let year = pick(if chosen.is_empty() { years } else { chosen });
+        random_day(year, if is_leap_year(year) { 3..=12 } else { 1..=12 })
+    }
+}
+
+fn random_day(year: i32, months: RangeInclusive<u32>) -> NaiveDate {
+    let first = NaiveDate::from_ymd_opt(year, random_range(months), 1).unwrap();
+    let days = first.checked_add_months(Months::new(1)).unwrap() - first;
+    first + Duration::days(random_range(0..days.num_days()))
+}
+
+#[test]
+fn adaptive_level_check() {
+    let mut adaptive = Adaptive::default();
+    for _ in 0..5 {
+        adaptive.record(true, 10.0);
+    }
+    assert_eq!(adaptive.level(), 1);
+    for _ in 0..4 {
+        adaptive.record(true, 10.0);
+    }
+    adaptive.record(false, 10.0);
+    assert_eq!(adaptive.level(), 2);
+    for _ in 0..3 {
+        adaptive.record(false, 10.0);
+    }
+    adaptive.record(true, 10.0);
+    adaptive.record(true, 10.0);
+    assert_eq!(adaptive.level(), 1);
+    for _ in 0..5 {
+        adaptive.record(true, 90.0);
+    }
+    assert_eq!(adaptive.level(), 0);
+    assert_eq!(adaptive.years(Method::ShakuntalaDevi), 1950..=2030);
+    adaptive.level = MAX_LEVEL;
+    assert_eq!(adaptive.years(Method::ShakuntalaDevi), 1584..=2203);
+    assert_eq!(adaptive.years(Method::ConwayDoomsday), 1800..=2199);
+}
+
+#[test]
+fn adaptive_history_check() {
+    use crate::session::Outcome;
+    let attempt = |mode, time| Attempt {
+        played_at: "2026-10-19T10:00:00Z".parse().unwrap(),
+        mode,
+        method: Method::ShakuntalaDevi,
+        date: NaiveDate::from_ymd_opt(1980, 2, 1),
+        challenge: "1980-02-01".to_string(),
+        answer: "Friday".to_string(),
+        guesses: vec!["Friday".to_string()],
+        hints: 0,
+        time,
+        result: Outcome::Found,
+    };
+    let day_games: Vec<Attempt> = (0..5).map(|_| attempt(Mode::Day, 10.0)).collect();
+    assert_eq!(Adaptive::from_history(&day_games).level(), 1);
+    //the blitz guesses are not day games
+    let blitz: Vec<Attempt> = (0..5).map(|_| attempt(Mode::Blitz, 2.0)).collect();
+    assert_eq!(Adaptive::from_history(&blitz).level(), 0);
+    assert_eq!(
+        Adaptive::from_history(&[day_games.clone(), blitz].concat()).level(),
+        1
+    );
+}
+
+#[test]
+fn adaptive_random_date_check() {
+    let beginner = Adaptive::default();
+    for _ in 0..200 {
+        let dt = beginner.random_date(Method::ShakuntalaDevi);
+        assert!(beginner
+            .years(Method::ShakuntalaDevi)
+            .contains(&(dt.year() as u32)));
+        assert!(
+            !(is_leap_year(dt.year()) && dt.month() <= 2),
+            "testing {}",
+            dt
+        );
+    }
+    let expert = Adaptive {
+        level: MAX_LEVEL,
+        recent: vec![],
+    };
+    let tricky = (0..1000)
+        .map(|_| expert.random_date(Method::ShakuntalaDevi))
+        .filter(|dt| is_leap_year(dt.year()) && dt.month() <= 2)
+        .count();
+    assert!(tricky > 200, "testing {}", tricky);
+}
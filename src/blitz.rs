@@ -0,0 +1,166 @@
+use chrono::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use web_time::Instant;
+
+use crate::session::{Challenge, Guess, Outcome, TrainingSession};
+use crate::Method;
+
+//in seconds
+pub const DURATIONS: [u64; 3] = [60, 120, 300];
+pub const POINTS: i32 = 1;
+pub const PENALTY: i32 = 1;
+
+//as many dates as possible before the time is up, a wrong guess costs a point and the next date comes
+#[derive(Debug, Clone)]
+pub struct Blitz {
+    pub duration: u64,
+    pub method: Method,
+    pub first_year: u32,
+    pub last_year: u32,
+    pub session: TrainingSession,
+    start: Instant,
+    found: u32,
+    wrong: u32,
+}
+
+impl Blitz {
+    pub fn new(duration: u64, method: Method, first_year: u32, last_year: u32) -> Blitz {
+        Blitz {
+            duration,
+            method,
+            first_year,
+            last_year,
+            session: TrainingSession::new(Challenge::blitz(
+                method,
+                crate::random_date(first_year, last_year),
+            )),
+            start: Instant::now(),
+            found: 0,
+            wrong: 0,
+        }
+    }
+
+    pub fn remaining(&self) -> Duration {
+        Duration::from_secs(self.duration).saturating_sub(self.start.elapsed())
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.remaining().is_zero()
+    }
+
+    //the date answered, None once the time is up
+    pub fn guess(&mut self, weekday: Weekday) -> Option<TrainingSession> {
+        if self.is_over() {
+            return None;
+        }
+        self.session.guess(Guess::Weekday(weekday))?;
+        if self.session.outcome() == Some(Outcome::Found) {
+            self.found += 1;
+        } else {
+            self.wrong += 1;
+            self.session.reveal();
+        }
+        let next = TrainingSession::new(Challenge::blitz(
+            self.method,
+            crate::random_date(self.first_year, self.last_year),
+        ));
+        Some(std::mem::replace(&mut self.session, next))
+    }
+
+    pub fn found(&self) -> u32 {
+        self.found
+    }
+
+    pub fn wrong(&self) -> u32 {
+        self.wrong
+    }
+
+    pub fn score(&self) -> i32 {
+        self.found as i32 * POINTS - self.wrong as i32 * PENALTY
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Best {
+    pub duration: u64,
+    pub first_year: u32,
+    pub last_year: u32,
+    pub score: i32,
+    pub played_at: DateTime<Utc>,
+}
+
+//one personal best for each duration and year range
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Bests {
+    bests: Vec<Best>,
+}
+
+impl Bests {
+    pub fn best(&self, duration: u64, first_year: u32, last_year: u32) -> Option<&Best> {
+        self.bests.iter().find(|best| {
+            (best.duration, best.first_year, best.last_year) == (duration, first_year, last_year)
+        })
+    }
+
+    //true when the score of the blitz is a new personal best
+    pub fn submit(&mut self, blitz: &Blitz, now: DateTime<Utc>) -> bool {
+        let new = Best {
+            duration: blitz.duration,
+            first_year: blitz.first_year,
+            last_year: blitz.last_year,
+            score: blitz.score(),
+            played_at: now,
+        };
+        match self.bests.iter_mut().find(|best| {
+            (best.duration, best.first_year, best.last_year)
+                == (new.duration, new.first_year, new.last_year)
+        }) {
+            Some(best) if best.score >= new.score => false,
+            Some(best) => {
+                *best = new;
+                true
+            }
+            None => {
+                self.bests.push(new);
+                true
+            }
+        }
+    }
+}
+
+#[test]
+fn blitz_check() {
+    use crate::session::{Expected, Mode};
+    let mut blitz = Blitz::new(60, Method::ShakuntalaDevi, 1900, 2000);
+    assert!(!blitz.is_over());
+    assert_eq!(blitz.session.challenge.mode, Mode::Blitz);
+    let answer = |blitz: &Blitz| match blitz.session.challenge.expected {
+        Expected::Weekday(weekday) => weekday,
+        _ => unreachable!(),
+    };
+    for _ in 0..3 {
+        let weekday = answer(&blitz);
+        let session = blitz.guess(weekday).unwrap();
+        assert_eq!(session.outcome(), Some(Outcome::Found));
+    }
+    let wrong = answer(&blitz).succ();
+    let session = blitz.guess(wrong).unwrap();
+    assert_eq!(session.outcome(), Some(Outcome::GaveUp));
+    assert_eq!(session.guesses(), &[Guess::Weekday(wrong)]);
+    assert_eq!((blitz.found(), blitz.wrong(), blitz.score()), (3, 1, 2));
+    assert_eq!(blitz.session.tries(), 0);
+
+    let mut over = Blitz::new(0, Method::ShakuntalaDevi, 1900, 2000);
+    assert!(over.is_over());
+    assert!(over.guess(Weekday::Mon).is_none());
+
+    let now: DateTime<Utc> = "2026-10-19T10:00:00Z".parse().unwrap();
+    let mut bests = Bests::default();
+    assert!(bests.submit(&blitz, now));
+    assert!(!bests.submit(&blitz, now));
+    assert_eq!(bests.best(60, 1900, 2000).unwrap().score, 2);
+    assert_eq!(bests.best(120, 1900, 2000), None);
+    assert!(bests.submit(&over, now));
+    assert_eq!(bests.best(0, 1900, 2000).unwrap().score, 0);
+}
@@ -1,66 +1,838 @@
 use chrono::prelude::*;
-use clap::Parser;
+use clap::parser::ValueSource;
+use clap::{error::ErrorKind, Args, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
 use num_traits::cast::FromPrimitive;
+use shakuntala_devi_trainer::adaptive::{Adaptive, MAX_LEVEL};
+use shakuntala_devi_trainer::blitz::{Blitz, DURATIONS};
+use shakuntala_devi_trainer::diagnosis::possible_mistakes;
+use shakuntala_devi_trainer::guided::GuidedSolve;
+use shakuntala_devi_trainer::session::{
+    self, Challenge, Expected, Guess, Mode, Outcome, TrainingSession,
+};
+use shakuntala_devi_trainer::{
+    Difficulty, Method, Numbering, TableDrill, YearRangeError, T2, YEARS,
+};
 use std::io;
-use std::time::Instant;
+use std::path::PathBuf;
+
+use crate::practice;
+use crate::protocol;
+use crate::report::{self, Mismatch, MonthEntry, Report, RoundReport, WeekdayCount, YearEntry};
+use crate::settings::Settings;
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum Table {
+    Month,
+    Year,
+}
 
 #[derive(Parser, Debug)]
-#[clap(name = "basic")]
-struct Opt {
-    #[clap(short, long)]
-    cli: bool,
+#[clap(name = "shakuntala-devi-trainer", version, about)]
+pub struct Opt {
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+    /// Same as the play command
+    #[clap(short, long, hide = true)]
+    pub cli: bool,
+    #[clap(flatten)]
+    pub shared: Shared,
+    #[clap(skip)]
+    pub settings: Settings,
+}
+
+#[derive(Args, Clone, Copy, Debug)]
+pub struct Shared {
+    /// First year of the random dates
+    #[clap(long, global = true, default_value_t = shakuntala_devi_trainer::DEFAULT_FIRST_YEAR)]
+    pub from: u32,
+    /// Last year of the random dates
+    #[clap(long, global = true, default_value_t = shakuntala_devi_trainer::DEFAULT_LAST_YEAR)]
+    pub to: u32,
+    /// Method used to find the day of the week
+    #[clap(long, global = true, value_enum, default_value_t)]
+    pub method: Method,
+    /// Seed to replay the same random dates
+    #[clap(long, global = true)]
+    pub seed: Option<u64>,
+    /// Output of solve, table, verify, stats and the play summary
+    #[clap(long, global = true, value_enum, default_value_t)]
+    pub format: Format,
+    /// Numbers of the weekdays in the answers
+    #[clap(long, global = true, value_enum, default_value_t)]
+    pub numbering: Numbering,
+    #[clap(skip = true)]
+    pub timer: bool,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Launch the GUI (default)
+    Gui,
+    /// Launch the full-screen terminal UI
+    #[cfg(not(target_arch = "wasm32"))]
+    Tui,
+    /// Play in the text console
+    Play {
+        #[clap(short, long, value_enum, default_value_t)]
+        mode: Mode,
+        #[clap(short, long, value_enum, default_value_t)]
+        difficulty: Difficulty,
+        /// Number of dates to play before the summary
+        #[clap(short, long, default_value_t = 1)]
+        rounds: u32,
+        /// Play until you quit
+        #[clap(short, long, conflicts_with = "rounds")]
+        endless: bool,
+        /// Exchange one JSON object per line with another program
+        #[clap(long, conflicts_with_all = ["rounds", "endless"])]
+        protocol: bool,
+        /// Adapt the years and the tricky dates of the day game to your last games
+        #[clap(short, long, conflicts_with = "protocol")]
+        adaptive: bool,
+    },
+    /// Find as many days as possible before the time is up
+    Blitz {
+        /// Seconds to play: 60, 120 or 300
+        #[clap(short, long, default_value_t = DURATIONS[0], value_parser = blitz_duration)]
+        duration: u64,
+    },
+    /// Find the day of the week of a date like 1980-02-01
+    Solve { date: NaiveDate },
+    /// Print the month table and the year table entries of the year range
+    Table {
+        #[clap(value_enum)]
+        table: Option<Table>,
+    },
+    /// Check the method against the calendar on the year range
+    Verify,
+    /// Print the accuracy and the speed of the practice history
+    Stats {
+        /// Print calendar statistics of the year range instead
+        #[clap(long)]
+        calendar: bool,
+    },
+    /// Export or import the practice history
+    History {
+        #[clap(subcommand)]
+        action: HistoryAction,
+    },
 }
 
-pub fn parse_cli() -> bool {
-    let opt = Opt::parse();
-    opt.cli
+#[derive(Subcommand, Debug)]
+pub enum HistoryAction {
+    /// Print the history as JSON lines or write it to a file
+    Export { file: Option<PathBuf> },
+    /// Add the attempts of a JSON lines file to the history
+    Import { file: PathBuf },
 }
 
-pub fn run_cli() {
-    let (random_date, shakuntala_devi_answer, tips) =
-        shakuntala_devi_trainer::random_date_with_tips(
-            shakuntala_devi_trainer::DEFAULT_FIRST_YEAR,
-            shakuntala_devi_trainer::DEFAULT_LAST_YEAR,
+//only the durations with a personal best
+fn blitz_duration(value: &str) -> Result<u64, String> {
+    value
+        .parse()
+        .ok()
+        .filter(|duration| DURATIONS.contains(duration))
+        .ok_or_else(|| format!("the duration is one of {:?}", DURATIONS))
+}
+
+//the settings file gives the values of the options missing on the command line
+pub fn parse_cli() -> Opt {
+    let matches = Opt::command().get_matches();
+    let mut opt = Opt::from_arg_matches(&matches).unwrap_or_else(|error| error.exit());
+    let mut settings = Settings::load();
+    let from_settings = |id| matches.value_source(id) != Some(ValueSource::CommandLine);
+    if from_settings("from") {
+        opt.shared.from = settings.first_year;
+    }
+    if from_settings("to") {
+        opt.shared.to = settings.last_year;
+    }
+    if from_settings("method") {
+        opt.shared.method = settings.method;
+    }
+    if from_settings("numbering") {
+        opt.shared.numbering = settings.numbering;
+    }
+    opt.shared.timer = settings.show_timer;
+    settings.first_year = opt.shared.from;
+    settings.last_year = opt.shared.to;
+    settings.method = opt.shared.method;
+    settings.numbering = opt.shared.numbering;
+    opt.settings = settings;
+    //the GUI, the terminal UI and the games other than the day game always use Shakuntala Devi's method
+    let method = match opt.command {
+        Some(Command::Gui)
+        | Some(Command::Play {
+            mode: Mode::DaysBetween | Mode::WeekdayDrill | Mode::Puzzle | Mode::Guided,
+            ..
+        }) => Method::ShakuntalaDevi,
+        #[cfg(not(target_arch = "wasm32"))]
+        Some(Command::Tui) => Method::ShakuntalaDevi,
+        None if !opt.cli => Method::ShakuntalaDevi,
+        _ => opt.shared.method,
+    };
+    let validation = match opt.command {
+        //a single date only needs the years of the method, some go beyond the year range
+        Some(Command::Solve { date }) => {
+            let year = u32::try_from(date.year()).unwrap_or_default();
+            if method.supported_years().contains(&year) {
+                Ok(())
+            } else {
+                Err(YearRangeError::Unsupported(method, year))
+            }
+        }
+        //only a range given on the command line is an error, a wrong one of the settings is replaced
+        _ => match shakuntala_devi_trainer::validate_year_range(
+            opt.shared.from,
+            opt.shared.to,
+            method,
+        ) {
+            Err(error) if from_settings("from") && from_settings("to") => {
+                eprintln!("Ignoring the year range: {}", error);
+                let years = method.supported_years();
+                opt.shared.from = shakuntala_devi_trainer::DEFAULT_FIRST_YEAR.max(*years.start());
+                opt.shared.to = shakuntala_devi_trainer::DEFAULT_LAST_YEAR.min(*years.end());
+                opt.settings.first_year = opt.shared.from;
+                opt.settings.last_year = opt.shared.to;
+                Ok(())
+            }
+            validation => validation,
+        },
+    };
+    if let Err(error) = validation {
+        Opt::command()
+            .error(ErrorKind::ValueValidation, error)
+            .exit()
+    }
+    opt
+}
+
+pub fn run_cli(command: Command, shared: &Shared) {
+    match command {
+        Command::Gui => unreachable!("the GUI is not a console command"),
+        #[cfg(not(target_arch = "wasm32"))]
+        Command::Tui => unreachable!("the terminal UI is not a console command"),
+        Command::Play {
+            mode,
+            difficulty,
+            protocol: true,
+            ..
+        } => protocol::run(mode, difficulty, shared),
+        Command::Play {
+            mode,
+            difficulty,
+            rounds,
+            endless,
+            adaptive,
+            ..
+        } => play(
+            mode,
+            difficulty,
+            (!endless).then_some(rounds),
+            adaptive,
+            shared,
+        ),
+        Command::Blitz { duration } => blitz(duration, shared),
+        Command::Solve { date } => solve(date, shared),
+        Command::Table { table } => print_table(table, shared),
+        Command::Verify => verify(shared),
+        Command::Stats { calendar: true } => calendar_stats(shared),
+        Command::Stats { calendar: false } => stats(shared),
+        Command::History { action } => history(action),
+    }
+}
+
+fn history(action: HistoryAction) {
+    let result = match &action {
+        HistoryAction::Export { file } => practice::export(file.as_deref()).map(|count| {
+            if file.is_some() {
+                println!("Exported {} attempts", count);
+            }
+        }),
+        HistoryAction::Import { file } => practice::import(file).map(|(count, skipped)| {
+            println!("Imported {} attempts, {} already there", count, skipped)
+        }),
+    };
+    if let Err(error) = result {
+        eprintln!("{}", error);
+        std::process::exit(1)
+    }
+}
+
+fn solve(date: NaiveDate, shared: &Shared) {
+    let (weekday, tips) = shared.method.solve(date);
+    if shared.format == Format::Json {
+        return report::print(Report::Solve {
+            date,
+            weekday: weekday.into(),
+            method: report::value_name(shared.method),
+            steps: Vec::from(tips.0),
+        });
+    }
+    println!(
+        "{} is a {} ({})",
+        date,
+        shakuntala_devi_trainer::weekday_name(weekday),
+        shared.method.name()
+    );
+    print!("{}", tips);
+}
+
+fn print_table(table: Option<Table>, shared: &Shared) {
+    let month_table: Vec<_> = match table {
+        None | Some(Table::Month) => T2
+            .iter()
+            .enumerate()
+            .map(|(month0, &entry)| MonthEntry {
+                month: Month::from_usize(month0 + 1).unwrap().name(),
+                entry,
+            })
+            .collect(),
+        Some(Table::Year) => vec![],
+    };
+    let year_table: Vec<_> = match table {
+        None | Some(Table::Year) => {
+            let mut years: Vec<_> = YEARS
+                .iter()
+                .filter(|(&year, _)| (shared.from as i32..=shared.to as i32).contains(&year))
+                .map(|(&year, &entry)| YearEntry { year, entry })
+                .collect();
+            years.sort_by_key(|entry| entry.year);
+            years
+        }
+        Some(Table::Month) => vec![],
+    };
+    if shared.format == Format::Json {
+        return report::print(Report::Table {
+            month_table,
+            year_table,
+        });
+    }
+    if !month_table.is_empty() {
+        println!("Month table");
+        for entry in month_table {
+            println!("{} {}", entry.month, entry.entry);
+        }
+    }
+    if !year_table.is_empty() {
+        println!("Year table");
+        for entry in year_table {
+            println!("{} {}", entry.year, entry.entry);
+        }
+    }
+}
+
+fn calendar(shared: &Shared) -> impl Iterator<Item = NaiveDate> {
+    let last = NaiveDate::from_ymd_opt(shared.to as i32, 12, 31).unwrap();
+    NaiveDate::from_ymd_opt(shared.from as i32, 1, 1)
+        .unwrap()
+        .iter_days()
+        .take_while(move |&dt| dt <= last)
+}
+
+fn verify(shared: &Shared) {
+    let mut checked = 0;
+    let mut errors = 0;
+    let mut mismatches = vec![];
+    for dt in calendar(shared) {
+        checked += 1;
+        let weekday = shared.method.weekday(dt);
+        if weekday != dt.weekday() {
+            errors += 1;
+            if shared.format == Format::Json {
+                mismatches.push(Mismatch {
+                    date: dt,
+                    found: weekday.into(),
+                    expected: dt.weekday().into(),
+                });
+            } else if errors <= 10 {
+                println!("{} found {} instead of {}", dt, weekday, dt.weekday());
+            }
+        }
+    }
+    if shared.format == Format::Json {
+        report::print(Report::Verify {
+            method: report::value_name(shared.method),
+            from: shared.from,
+            to: shared.to,
+            checked,
+            errors,
+            mismatches,
+        });
+    } else {
+        println!(
+            "{} found {} wrong days out of {} from {} to {}",
+            shared.method.name(),
+            errors,
+            checked,
+            shared.from,
+            shared.to
         );
-    let mut tips = tips.0.iter();
-    let mut tries = 0;
-    if shakuntala_devi_answer != random_date.weekday() {
-        println!("Shakuntala Devi cannot found the day of {:#?}", random_date);
-        std::process::exit(-1)
     }
-    let start = Instant::now();
+    if errors > 0 {
+        std::process::exit(1)
+    }
+}
+
+fn calendar_stats(shared: &Shared) {
+    let mut days = [0; 7];
+    let mut friday_13 = 0;
+    for dt in calendar(shared) {
+        days[dt.weekday().num_days_from_monday() as usize] += 1;
+        if dt.day() == 13 && dt.weekday() == Weekday::Fri {
+            friday_13 += 1;
+        }
+    }
+    let leap_years = (shared.from as i32..=shared.to as i32)
+        .filter(|&year| NaiveDate::from_ymd_opt(year, 2, 29).is_some())
+        .count();
+    if shared.format == Format::Json {
+        return report::print(Report::Calendar {
+            from: shared.from,
+            to: shared.to,
+            leap_years,
+            friday_13,
+            weekdays: days
+                .iter()
+                .enumerate()
+                .map(|(weekday, &days)| WeekdayCount {
+                    weekday: Weekday::from_usize(weekday).unwrap().into(),
+                    days,
+                })
+                .collect(),
+        });
+    }
+    println!("Years from {} to {}", shared.from, shared.to);
+    println!("Leap years {}", leap_years);
+    println!("Friday 13th {}", friday_13);
+    for (weekday, count) in days.iter().enumerate() {
+        println!(
+            "{} {}",
+            shakuntala_devi_trainer::weekday_name(Weekday::from_usize(weekday).unwrap()),
+            count
+        );
+    }
+}
+
+fn stats(shared: &Shared) {
+    let attempts = practice::load();
+    let breakdowns = shakuntala_devi_trainer::stats::breakdowns(&attempts);
+    if shared.format == Format::Json {
+        return report::print(Report::Stats {
+            attempts: attempts.len(),
+            breakdowns,
+        });
+    }
+    if attempts.is_empty() {
+        println!("No attempt in the history yet, play to fill it");
+        return;
+    }
+    println!("{} attempts", attempts.len());
+    for breakdown in breakdowns
+        .iter()
+        .filter(|breakdown| !breakdown.lines.is_empty())
+    {
+        println!();
+        println!(
+            "{:<16}{:>10}{:>10}{:>13}",
+            format!("Per {}", breakdown.by.replace('_', " ")),
+            "Attempts",
+            "Accuracy",
+            "Median time"
+        );
+        for line in &breakdown.lines {
+            println!(
+                "{:<16}{:>10}{:>9.0}%{:>13}",
+                line.label,
+                line.attempts,
+                100.0 * line.accuracy,
+                line.median_time
+                    .map_or("-".to_string(), |time| format!("{:.1}s", time))
+            );
+        }
+    }
+}
+
+//None at the end of the input
+fn read_line() -> Option<String> {
+    let mut line = String::new();
+    match io::stdin()
+        .read_line(&mut line)
+        .expect("Failed to read line")
+    {
+        0 => None,
+        _ => Some(line.trim().to_string()),
+    }
+}
+
+fn play(mode: Mode, difficulty: Difficulty, rounds: Option<u32>, adaptive: bool, shared: &Shared) {
+    let mut results = vec![];
+    let challenge = || match mode {
+        Mode::Day if adaptive => adaptive_challenge(shared),
+        _ => new_challenge(mode, difficulty, shared),
+    };
+    println!("Commands: h hint, s skip, ? solution, q quit");
+    //a round left with q or the end of the input is given up
+    loop {
+        let (round, quit) = play_round(challenge(), shared);
+        practice::record(&round);
+        results.push(round);
+        if quit || rounds.is_some_and(|rounds| results.len() as u32 >= rounds) {
+            break;
+        }
+        println!("Press Enter to continue or q to quit");
+        if read_line().is_none_or(|answer| answer == "q") {
+            break;
+        }
+    }
+    if shared.format == Format::Json {
+        print_json_summary(mode, &results);
+    } else if !results.is_empty() {
+        print_summary(&results);
+    }
+}
+
+//the time is checked after each answer, an answer given too late does not count
+fn blitz(duration: u64, shared: &Shared) {
+    let mut blitz = Blitz::new(duration, shared.method, shared.from, shared.to);
+    println!(
+        "Blitz of {}s from {} to {}, {} point for a day found and {} off for a wrong one",
+        duration,
+        shared.from,
+        shared.to,
+        shakuntala_devi_trainer::blitz::POINTS,
+        shakuntala_devi_trainer::blitz::PENALTY
+    );
+    if let Some(best) = practice::blitz_best(duration, shared.from, shared.to) {
+        println!("Personal best {}", best);
+    }
+    println!("Commands: q quit");
+    while !blitz.is_over() {
+        println!(
+            "{} ? {}s left",
+            blitz.session.challenge.challenge,
+            blitz.remaining().as_secs()
+        );
+        let answer = match read_line() {
+            Some(answer) if answer != "q" && answer != "quit" => answer,
+            _ => return,
+        };
+        let weekday = match blitz
+            .session
+            .challenge
+            .parse_with(&answer, shared.numbering)
+        {
+            Some(Guess::Weekday(weekday)) => weekday,
+            _ => continue,
+        };
+        match blitz.guess(weekday) {
+            Some(session) => {
+                practice::record(&session);
+                if session.outcome() == Some(Outcome::Found) {
+                    println!("Right");
+                } else {
+                    println!("Wrong, it was {}", session.challenge.answer);
+                }
+            }
+            None => println!("Too late"),
+        }
+    }
+    println!(
+        "Time is up ! Score {}: {} found and {} wrong",
+        blitz.score(),
+        blitz.found(),
+        blitz.wrong()
+    );
+    if practice::record_blitz(&blitz) {
+        println!("New personal best !");
+    }
+}
+
+fn print_json_summary(mode: Mode, results: &[TrainingSession]) {
+    let score = session::score(results);
+    report::print(Report::Play {
+        mode: report::value_name(mode),
+        rounds: results
+            .iter()
+            .enumerate()
+            .map(|(round, result)| RoundReport {
+                round: round + 1,
+                method: report::value_name(result.challenge.method),
+                challenge: result.challenge.challenge.clone(),
+                answer: result.challenge.answer.clone(),
+                weekday: match result.challenge.expected {
+                    Expected::Weekday(weekday) => Some(weekday.into()),
+                    _ => None,
+                },
+                steps: result.challenge.tips.0.iter().cloned().collect(),
+                tries: result.tries(),
+                hints: result.hints(),
+                time: result.elapsed().as_secs_f64(),
+                result: match result.outcome() {
+                    Some(Outcome::Found) => "found",
+                    Some(Outcome::Skipped) => "skipped",
+                    Some(Outcome::TimedOut) => "timed_out",
+                    _ => "gave_up",
+                },
+            })
+            .collect(),
+        first_guess: score.first_guess,
+        tries: score.tries,
+        accuracy: score.accuracy,
+        average_time: score.average_time.as_secs_f64(),
+    });
+}
+
+fn print_summary(results: &[TrainingSession]) {
+    println!(
+        "{:<6}{:<6}{:<6}{:<8}{:<8}Challenge",
+        "Round", "Tries", "Hints", "Time", "Result"
+    );
+    for (round, result) in results.iter().enumerate() {
+        println!(
+            "{:<6}{:<6}{:<6}{:<8}{:<8}{}",
+            round + 1,
+            result.tries(),
+            result.hints(),
+            format!("{:.1}s", result.elapsed().as_secs_f64()),
+            format!("{:?}", result.outcome().unwrap_or(Outcome::GaveUp)),
+            result.challenge.challenge
+        );
+    }
+    let score = session::score(results);
+    println!(
+        "Accuracy {:.0}% ({} of {} found at the first guess, {} guesses in total)",
+        100.0 * score.accuracy,
+        score.first_guess,
+        score.rounds,
+        score.tries
+    );
+    println!(
+        "Average response time {:.1}s",
+        score.average_time.as_secs_f64()
+    );
+}
+
+pub fn new_challenge(mode: Mode, difficulty: Difficulty, shared: &Shared) -> Challenge {
+    try_new_challenge(mode, difficulty, shared).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        std::process::exit(-1)
+    })
+}
+
+//an error when the method cannot find the day of the date drawn
+pub fn try_new_challenge(
+    mode: Mode,
+    difficulty: Difficulty,
+    shared: &Shared,
+) -> Result<Challenge, String> {
+    Ok(match mode {
+        Mode::Day => checked_day_challenge(
+            shared,
+            shakuntala_devi_trainer::random_date(shared.from, shared.to),
+        )?,
+        Mode::DaysBetween => {
+            let (first_date, second_date, _, _) =
+                shakuntala_devi_trainer::random_date_pair_with_tips(shared.from, shared.to);
+            Challenge::days_between(first_date, second_date)
+        }
+        Mode::WeekdayDrill => Challenge::weekday_drill(
+            &shakuntala_devi_trainer::random_weekday_drill(difficulty, shared.from, shared.to),
+        ),
+        Mode::Puzzle => Challenge::puzzle(shakuntala_devi_trainer::puzzle::random_puzzle(
+            shared.from,
+            shared.to,
+        )),
+        Mode::MonthTable => practice::table_challenge(TableDrill::Month, shared.from, shared.to),
+        Mode::YearTable => practice::table_challenge(TableDrill::Year, shared.from, shared.to),
+        Mode::CenturyTable => {
+            practice::table_challenge(TableDrill::Century, shared.from, shared.to)
+        }
+        Mode::Guided => {
+            Challenge::guided(shakuntala_devi_trainer::random_date(shared.from, shared.to))
+        }
+        Mode::Blitz => unreachable!("the blitz is played with the blitz command"),
+    })
+}
+
+//the level is replayed from the history so it goes on from the last session
+fn adaptive_challenge(shared: &Shared) -> Challenge {
+    let adaptive = Adaptive::from_history(&practice::load());
+    let years = adaptive.years(shared.method);
     println!(
-        "Shakuntala Devi found the day of {:#?} can you to ?",
-        random_date
+        "Level {} of {}, years {} to {}",
+        adaptive.level(),
+        MAX_LEVEL,
+        years.start(),
+        years.end()
     );
+    day_challenge(shared, adaptive.random_date(shared.method))
+}
+
+fn day_challenge(shared: &Shared, random_date: NaiveDate) -> Challenge {
+    checked_day_challenge(shared, random_date).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        std::process::exit(-1)
+    })
+}
+
+fn checked_day_challenge(shared: &Shared, random_date: NaiveDate) -> Result<Challenge, String> {
+    let challenge = Challenge::day(shared.method, random_date);
+    if challenge.expected != Expected::Weekday(random_date.weekday()) {
+        return Err(format!(
+            "{} cannot found the day of {:#?}",
+            shared.method.name(),
+            random_date
+        ));
+    }
+    Ok(challenge)
+}
+
+//unreadable lines are ignored, true when the player quits and the session is given up
+fn play_round(challenge: Challenge, shared: &Shared) -> (TrainingSession, bool) {
+    if challenge.mode == Mode::Guided {
+        return guided_round(challenge, shared);
+    }
+    println!("{}", challenge.question);
+    let mut session = TrainingSession::new(challenge);
     loop {
-        let mut guess = String::new();
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("Failed to read line");
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => continue,
+        let guess = match read_line() {
+            Some(guess) if guess != "q" && guess != "quit" => guess,
+            _ => {
+                session.give_up();
+                return (session, true);
+            }
         };
-        if !(0..7).contains(&guess) {
-            continue;
+        match guess.as_str() {
+            "h" | "hint" => {
+                match session.hint() {
+                    Some(tips) => println!("tips: {:#?}", tips),
+                    None => println!("Sorry, no more tips"),
+                };
+                continue;
+            }
+            "s" | "skip" => {
+                session.skip();
+                println!("Skipped, the answer was {}", session.challenge.answer);
+                break;
+            }
+            "?" | "solution" => {
+                print!("{}", session.reveal());
+                println!("The answer was {}", session.challenge.answer);
+                break;
+            }
+            _ => (),
         }
-        let guess = Weekday::from_u32(guess).unwrap().pred();
+        let guess = match session.challenge.parse_with(&guess, shared.numbering) {
+            Some(guess) => guess,
+            None => continue,
+        };
         println!("Your answer is {}", guess);
-        tries += 1;
-        if guess == shakuntala_devi_answer {
-            println!(
-                "Congratulation ! You found {} after {} guess in {:#?}s",
-                guess,
-                tries,
-                start.elapsed().as_secs()
-            );
+        let feedback = match session.guess(guess.clone()) {
+            Some(feedback) => feedback,
+            None => {
+                println!("Already tried");
+                continue;
+            }
+        };
+        if feedback.correct {
+            if shared.timer {
+                println!(
+                    "Congratulation ! You found {} after {} guess in {:#?}s",
+                    guess,
+                    feedback.tries,
+                    session.elapsed().as_secs()
+                );
+            } else {
+                println!(
+                    "Congratulation ! You found {} after {} guess",
+                    guess, feedback.tries
+                );
+            }
             break;
         } else {
-            match tips.next() {
+            if let Some(mistakes) = possible_mistakes(&feedback.mistakes) {
+                println!("{}", mistakes);
+            }
+            match feedback.tip {
                 Some(tips) => println!("tips: {:#?}", tips),
                 None => println!("Sorry, no more tips"),
             };
         }
     }
+    (session, false)
+}
+
+//every step is answered with a number, the last one with the weekday number
+fn guided_round(challenge: Challenge, shared: &Shared) -> (TrainingSession, bool) {
+    println!("{}", challenge.question);
+    let mut solve = GuidedSolve::new(challenge.date.unwrap());
+    while let Some(step) = solve.step() {
+        println!("{}", step.question);
+        let answer = match read_line() {
+            Some(answer) if answer != "q" && answer != "quit" => answer,
+            _ => {
+                solve.session.give_up();
+                return (solve.session, true);
+            }
+        };
+        match answer.as_str() {
+            "h" | "hint" => {
+                if let Some(tips) = solve.hint() {
+                    println!("tips: {:#?}", tips);
+                }
+                continue;
+            }
+            "s" | "skip" => {
+                solve.session.skip();
+                println!("Skipped, the answer was {}", solve.session.challenge.answer);
+                break;
+            }
+            "?" | "solution" => {
+                for step in &solve.steps[solve.found().len()..] {
+                    println!("{}", step.explanation);
+                }
+                solve.session.reveal();
+                println!("The answer was {}", solve.session.challenge.answer);
+                break;
+            }
+            _ => (),
+        }
+        let value = match answer.parse::<i32>() {
+            Ok(value) => value,
+            Err(_) => continue,
+        };
+        let feedback = solve.answer(value).unwrap();
+        if feedback.correct {
+            println!("Right");
+            continue;
+        }
+        if let Some(mistakes) = possible_mistakes(&feedback.mistakes) {
+            println!("{}", mistakes);
+        }
+        if let Some(explanation) = feedback.explanation {
+            println!("Not quite, {}", explanation);
+        }
+    }
+    if solve.session.outcome() == Some(Outcome::Found) {
+        let time = if shared.timer {
+            format!(" in {:#?}s", solve.session.elapsed().as_secs())
+        } else {
+            String::new()
+        };
+        println!(
+            "Congratulation ! You found {} with {} wrong steps{}",
+            solve.session.challenge.answer,
+            solve.errors(),
+            time
+        );
+    }
+    (solve.session, false)
 }
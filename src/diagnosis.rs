@@ -0,0 +1,178 @@
+use chrono::prelude::*;
+use std::fmt;
+
+use crate::{century_offset, shakuntala_devi, table_leap_year};
+
+//a step of Shakuntala Devi's method that gives a wrong weekday when it is missed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mistake {
+    //January and February of a leap year need one day less
+    LeapYearCorrection { year: i32 },
+    //the day subtracted anyway in another month or in a common year
+    NeedlessLeapYearCorrection { year: i32, month: u32 },
+    //the entry of the nearest leap year taken without the years since
+    YearsSince { leap_year: i32, years: i32 },
+    //the year table entries include the offset of their century
+    CenturyOffset { century: i32, offset: i32 },
+    MonthEntry { month: u32, entry: i32 },
+}
+
+impl fmt::Display for Mistake {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Mistake::LeapYearCorrection { year } => write!(
+                f,
+                "{} is a leap year, subtract 1 in January and February",
+                year
+            ),
+            Mistake::NeedlessLeapYearCorrection { year, month } if month <= 2 => write!(
+                f,
+                "{} is not a leap year, there is nothing to subtract",
+                year
+            ),
+            Mistake::NeedlessLeapYearCorrection { .. } => write!(
+                f,
+                "only January and February of a leap year need one day less"
+            ),
+            Mistake::YearsSince { leap_year, years } => write!(
+                f,
+                "add the {} years since {} to its year table entry",
+                years, leap_year
+            ),
+            Mistake::CenturyOffset { century, offset } => write!(
+                f,
+                "the year table entries of the {}s include the century offset {}",
+                century * 100,
+                offset
+            ),
+            Mistake::MonthEntry { month, entry } => write!(
+                f,
+                "the month table entry of {} is {}",
+                Month::try_from(month as u8).unwrap().name(),
+                entry
+            ),
+        }
+    }
+}
+
+//the days each mistake adds to the right answer when it applies to the date
+fn candidates(dt: NaiveDate) -> Vec<(Mistake, i32)> {
+    let year = dt.year();
+    let (leap_year, direct_leap_year) = table_leap_year(year);
+    let mut candidates = vec![];
+    if direct_leap_year && dt.month() <= 2 {
+        candidates.push((Mistake::LeapYearCorrection { year }, 1));
+    }
+    if !direct_leap_year {
+        let years = year - leap_year;
+        candidates.push((Mistake::YearsSince { leap_year, years }, -years));
+    }
+    let century = year / 100;
+    let offset = century_offset(century);
+    if offset != 0 {
+        candidates.push((Mistake::CenturyOffset { century, offset }, -offset));
+    }
+    let month = dt.month();
+    let entry = crate::T2[dt.month0() as usize];
+    candidates.push((Mistake::MonthEntry { month, entry }, 1));
+    candidates.push((Mistake::MonthEntry { month, entry }, -1));
+    if !(direct_leap_year && month <= 2) {
+        candidates.push((Mistake::NeedlessLeapYearCorrection { year, month }, -1));
+    }
+    candidates
+}
+
+//the mistakes that give the wrong weekday in the order they are checked, nothing tells them apart
+pub fn diagnose(dt: NaiveDate, guess: Weekday) -> Vec<Mistake> {
+    let (weekday, _) = shakuntala_devi(dt);
+    let days = guess.num_days_from_monday() as i32 - weekday.num_days_from_monday() as i32;
+    candidates(dt)
+        .into_iter()
+        .filter(|(_, offset)| (days - offset).rem_euclid(7) == 0)
+        .map(|(mistake, _)| mistake)
+        .collect()
+}
+
+//one line for the front-ends, None without a mistake
+pub fn possible_mistakes(mistakes: &[Mistake]) -> Option<String> {
+    match mistakes {
+        [] => None,
+        [mistake] => Some(format!("Possible mistake: {}", mistake)),
+        _ => Some(format!(
+            "Possible mistakes: {}",
+            mistakes
+                .iter()
+                .map(|mistake| mistake.to_string())
+                .collect::<Vec<_>>()
+                .join(" or ")
+        )),
+    }
+}
+
+#[test]
+fn diagnose_check() {
+    let dt = NaiveDate::from_ymd_opt(1980, 2, 1).unwrap();
+    assert_eq!(shakuntala_devi(dt).0, Weekday::Fri);
+    assert_eq!(
+        diagnose(dt, Weekday::Sat),
+        vec![
+            Mistake::LeapYearCorrection { year: 1980 },
+            Mistake::MonthEntry { month: 2, entry: 3 }
+        ]
+    );
+    assert_eq!(
+        diagnose(dt, Weekday::Thu),
+        vec![Mistake::MonthEntry { month: 2, entry: 3 }]
+    );
+    assert_eq!(diagnose(dt, Weekday::Mon), vec![]);
+
+    let dt = NaiveDate::from_ymd_opt(2023, 7, 14).unwrap();
+    assert_eq!(shakuntala_devi(dt).0, Weekday::Fri);
+    assert_eq!(
+        diagnose(dt, Weekday::Tue),
+        vec![Mistake::YearsSince {
+            leap_year: 2020,
+            years: 3
+        }]
+    );
+    assert_eq!(
+        diagnose(dt, Weekday::Thu),
+        vec![
+            Mistake::MonthEntry { month: 7, entry: 6 },
+            Mistake::NeedlessLeapYearCorrection {
+                year: 2023,
+                month: 7
+            }
+        ]
+    );
+    assert_eq!(
+        diagnose(dt, Weekday::Sat),
+        vec![
+            Mistake::CenturyOffset {
+                century: 20,
+                offset: 6
+            },
+            Mistake::MonthEntry { month: 7, entry: 6 }
+        ]
+    );
+    assert_eq!(
+        Mistake::YearsSince {
+            leap_year: 2020,
+            years: 3
+        }
+        .to_string(),
+        "add the 3 years since 2020 to its year table entry"
+    );
+    assert_eq!(possible_mistakes(&[]), None);
+    assert_eq!(
+        possible_mistakes(&diagnose(dt, Weekday::Sat)).unwrap(),
+        format!(
+            "Possible mistakes: {} or {}",
+            Mistake::CenturyOffset {
+                century: 20,
+                offset: 6
+            },
+            Mistake::MonthEntry { month: 7, entry: 6 }
+        )
+    );
+}
@@ -5,20 +5,23 @@ pub mod style;
 use self::app::ShakuntalaDeviTrainer;
 use iced::Size;
 
-pub fn run_gui() {
+use crate::settings::Settings;
+
+pub fn run_gui(settings: Settings) {
     let app = iced::application(
-        ShakuntalaDeviTrainer::new,
+        move || ShakuntalaDeviTrainer::new(settings.clone()),
         ShakuntalaDeviTrainer::update,
         ShakuntalaDeviTrainer::view,
     )
     .theme(ShakuntalaDeviTrainer::theme)
+    .subscription(ShakuntalaDeviTrainer::subscription)
     .settings(iced::Settings {
         ..Default::default()
     })
     .title(ShakuntalaDeviTrainer::title)
     .window(iced::window::Settings {
-        size: Size::new(600f32, 350f32),
-        min_size: Some(Size::new(600f32, 350f32)),
+        size: Size::new(800f32, 450f32),
+        min_size: Some(Size::new(800f32, 450f32)),
         exit_on_close_request: true,
         icon: match image::load_from_memory(include_bytes!("../assets/calendar.png")) {
             Ok(buffer) => {
@@ -1,77 +1,490 @@
 use chrono::prelude::*;
-use enum_map::EnumMap;
+use enum_map::{Enum, EnumMap};
 use num_traits::cast::FromPrimitive;
 
 use iced::{
     alignment,
-    widget::{button, column, row, text, Container, Slider},
-    Alignment, Element, Length, Task,
+    keyboard::{self, key::Named, Key, Modifiers},
+    widget::{button, column, row, scrollable, text, text_input, Container, Slider},
+    Alignment, Element, Length, Subscription, Task,
 };
+use std::time::Duration;
 
-use shakuntala_devi_trainer::{shakuntala_devi_nearest_leap_year, Tips, T2, YEARS};
+use shakuntala_devi_trainer::adaptive::{Adaptive, MAX_LEVEL};
+use shakuntala_devi_trainer::blitz::{Blitz, DURATIONS};
+use shakuntala_devi_trainer::diagnosis::possible_mistakes;
+use shakuntala_devi_trainer::guided::GuidedSolve;
+use shakuntala_devi_trainer::session::{Challenge, Feedback, Guess, Outcome, TrainingSession};
+use shakuntala_devi_trainer::stats::{self, Breakdown};
+use shakuntala_devi_trainer::{puzzle::Puzzle, Difficulty, Method, TableDrill};
 
 use crate::gui::common::Screen;
+use crate::practice::{self, Practice};
+use crate::settings::Settings;
 
-use super::style::{button_day, button_start, slider_style};
+use super::style::{button_day, button_start, slider_style, Palette};
 
-//Weekday does not implement Default so we can't derive Default
+//TrainingSession does not implement Default so we can't derive Default
 #[derive(Debug, Clone)]
 pub(crate) struct ShakuntalaDeviTrainer {
     screen: Screen,
     first_year: u32,
     last_year: u32,
     random_date: NaiveDate,
-    week_day: Weekday,
-    game_answers: [bool; 7],
-    month_table_answers: [bool; 7],
-    year_table_answers: [bool; 13],
-    tips: Tips,
+    game: TrainingSession,
+    month_table: TrainingSession,
+    year_table: TrainingSession,
+    first_date: NaiveDate,
+    second_date: NaiveDate,
+    days_between: TrainingSession,
+    days_between_input: String,
+    difficulty: Difficulty,
+    weekday_drill: TrainingSession,
+    puzzle: Puzzle,
+    puzzle_session: TrainingSession,
+    puzzle_input: String,
+    guided: GuidedSolve,
+    guided_input: String,
+    blitz: Option<Blitz>,
+    blitz_duration: u64,
+    blitz_best: Option<i32>,
+    first_year_input: String,
+    last_year_input: String,
+    range_error: Option<String>,
+    practice: Practice,
+    statistics: Vec<Breakdown>,
+    adaptive: Adaptive,
     hint: EnumMap<Screen, String>,
-    start: web_time::Instant,
+    settings: Settings,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    Lifetime,
+    TwentiethCentury,
+    TwentyFirstCentury,
+    FullRange,
+}
+
+impl Preset {
+    const ALL: [Preset; 4] = [
+        Preset::Lifetime,
+        Preset::TwentiethCentury,
+        Preset::TwentyFirstCentury,
+        Preset::FullRange,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Preset::Lifetime => "MY LIFETIME",
+            Preset::TwentiethCentury => "20TH CENTURY",
+            Preset::TwentyFirstCentury => "21ST CENTURY",
+            Preset::FullRange => "FULL RANGE",
+        }
+    }
+
+    //the lifetime goes from the birth year of the settings to this year
+    fn years(self, birth_year: Option<u32>) -> Result<(u32, u32), String> {
+        match self {
+            Preset::Lifetime => match birth_year {
+                Some(birth_year) => Ok((birth_year, Utc::now().year() as u32)),
+                None => Err("Set birth_year in settings.toml to use this preset".to_string()),
+            },
+            Preset::TwentiethCentury => Ok((1900, 1999)),
+            Preset::TwentyFirstCentury => Ok((2000, 2099)),
+            Preset::FullRange => {
+                let years = Method::ShakuntalaDevi.supported_years();
+                Ok((*years.start(), *years.end()))
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
     GuessDay(Weekday),
+    GuessDrillDay(Weekday),
+    DrillDifficulty(Difficulty),
     GuessMonthTable(i32),
     GuessYearTable(i32),
+    DaysBetweenInput(String),
+    GuessDaysBetween,
+    PuzzleInput(String),
+    GuessPuzzle,
+    GuidedInput(String),
+    GuessGuided,
+    BlitzDuration(u64),
+    StartBlitz,
+    GuessBlitz(Weekday),
     Reset,
     FirstYear(u32),
     LastYear(u32),
+    FirstYearInput(String),
+    LastYearInput(String),
+    SubmitYears,
+    Preset(Preset),
+    SaveSettings,
+    Adaptive,
+    Countdown(u64),
+    Theme,
+    Tick,
+    Key(Key, Modifiers),
     GameMode,
     TrainingMonthTableMode,
     TrainingYearTableMode,
+    DaysBetweenMode,
+    WeekdayDrillMode,
+    PuzzleMode,
+    GuidedMode,
+    BlitzMode,
+    StatisticsMode,
     SolutionMode,
 }
 
+fn initial_hint() -> EnumMap<Screen, String> {
+    enum_map! {
+        Screen::Game => "Guess the day!".to_string(),
+        Screen::Solution => "".to_string(),
+        Screen::TrainingMonthTable => "Which entry is the good one ?".to_string(),
+        Screen::TrainingYearTable => "Which entry is the good one ?".to_string(),
+        Screen::DaysBetween => "How many days between these dates ?".to_string(),
+        Screen::WeekdayDrill => "Which day is it ?".to_string(),
+        Screen::Puzzle => "Solve the puzzle!".to_string(),
+        Screen::Guided => "Find the day one step at a time".to_string(),
+        Screen::Blitz => "Find as many days as possible before the time is up".to_string(),
+        Screen::Statistics => "".to_string(),
+    }
+}
+
+fn found_hint(found: String, tries: u32, time: Option<u64>) -> String {
+    match time {
+        Some(time) => format!(
+            "Congratulation ! You {} after {} guess in {:#?}s",
+            found, tries, time
+        ),
+        None => format!("Congratulation ! You {} after {} guess", found, tries),
+    }
+}
+
+fn tip_hint(tip: Option<String>) -> String {
+    match tip {
+        Some(tips) => format!("tips: {:#?}", tips),
+        None => "Sorry, no more tips".to_string(),
+    }
+}
+
+//the possible mistakes of a wrong weekday come before the tip
+fn mistake_hint(feedback: Feedback) -> String {
+    match possible_mistakes(&feedback.mistakes) {
+        Some(mistakes) => format!("{}\n{}", mistakes, tip_hint(feedback.tip)),
+        None => tip_hint(feedback.tip),
+    }
+}
+
+fn weekday_drill_session(
+    difficulty: Difficulty,
+    first_year: u32,
+    last_year: u32,
+) -> TrainingSession {
+    TrainingSession::new(Challenge::weekday_drill(
+        &shakuntala_devi_trainer::random_weekday_drill(difficulty, first_year, last_year),
+    ))
+}
+
+//the message of the menu button of each screen
+fn mode(screen: Screen) -> Message {
+    match screen {
+        Screen::Game => Message::GameMode,
+        Screen::Solution => Message::SolutionMode,
+        Screen::TrainingMonthTable => Message::TrainingMonthTableMode,
+        Screen::TrainingYearTable => Message::TrainingYearTableMode,
+        Screen::DaysBetween => Message::DaysBetweenMode,
+        Screen::WeekdayDrill => Message::WeekdayDrillMode,
+        Screen::Puzzle => Message::PuzzleMode,
+        Screen::Guided => Message::GuidedMode,
+        Screen::Blitz => Message::BlitzMode,
+        Screen::Statistics => Message::StatisticsMode,
+    }
+}
+
+//R for Thursday and U for Sunday so every initial is a different weekday
+fn weekday_initial(key: &str) -> Option<Weekday> {
+    match key.to_lowercase().as_str() {
+        "m" => Some(Weekday::Mon),
+        "t" => Some(Weekday::Tue),
+        "w" => Some(Weekday::Wed),
+        "r" => Some(Weekday::Thu),
+        "f" => Some(Weekday::Fri),
+        "s" => Some(Weekday::Sat),
+        "u" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+//the answers already tried, or all of them once the session is over
+fn weekday_answers(session: &TrainingSession) -> [bool; 7] {
+    std::array::from_fn(|i| !session.can_guess(&Guess::Weekday(Weekday::from_usize(i).unwrap())))
+}
+
+fn entry_answers(session: &TrainingSession) -> [bool; 7] {
+    std::array::from_fn(|i| !session.can_guess(&Guess::Entry(i as i32)))
+}
+
+fn check_table(
+    session: &mut TrainingSession,
+    practice: &mut Practice,
+    guess: i32,
+) -> Option<String> {
+    let feedback = session.guess(Guess::Entry(guess))?;
+    Some(if feedback.correct {
+        practice.record(session);
+        format!("Congratulation ! {} is the right answer", guess)
+    } else {
+        match feedback.tip {
+            Some(tips) => format!("Try again. Tips: {}", tips),
+            None => "Try again".to_string(),
+        }
+    })
+}
+
 impl ShakuntalaDeviTrainer {
-    pub fn new() -> (ShakuntalaDeviTrainer, Task<Message>) {
-        let (random_date, shakuntala_devi_answer, tips) =
-            shakuntala_devi_trainer::random_date_with_tips(
-                shakuntala_devi_trainer::DEFAULT_FIRST_YEAR,
-                shakuntala_devi_trainer::DEFAULT_LAST_YEAR,
+    pub fn new(settings: Settings) -> (ShakuntalaDeviTrainer, Task<Message>) {
+        let mut trainer = Self {
+            screen: settings.screen,
+            ..Self::start(
+                settings.first_year,
+                settings.last_year,
+                Difficulty::default(),
+                settings,
+                Practice::load(),
+            )
+        };
+        trainer.sync_year_inputs();
+        (trainer, iced::Task::none())
+    }
+
+    //every screen gets a new challenge, the year table drill has its own year range
+    fn start(
+        first_year: u32,
+        last_year: u32,
+        difficulty: Difficulty,
+        settings: Settings,
+        practice: Practice,
+    ) -> ShakuntalaDeviTrainer {
+        //the adaptive level replaces the year range of the game
+        let adaptive = Adaptive::from_history(&practice.attempts);
+        let random_date = if settings.adaptive {
+            adaptive.random_date(Method::ShakuntalaDevi)
+        } else {
+            shakuntala_devi_trainer::random_date(first_year, last_year)
+        };
+        let (first_date, second_date, _, _) =
+            shakuntala_devi_trainer::random_date_pair_with_tips(first_year, last_year);
+        let puzzle = shakuntala_devi_trainer::puzzle::random_puzzle(first_year, last_year);
+        //the table drills ask the cards chosen by the spaced repetition
+        let month_table = TrainingSession::new(practice.table_challenge(
+            TableDrill::Month,
+            first_year,
+            last_year,
+        ));
+        let year_table = TrainingSession::new(practice.table_challenge(
+            TableDrill::Year,
+            settings.year_table_first_year,
+            settings.year_table_last_year,
+        ));
+        let mut hint = initial_hint();
+        hint[Screen::TrainingMonthTable] = month_table.challenge.question.clone();
+        hint[Screen::TrainingYearTable] = year_table.challenge.question.clone();
+        Self {
+            screen: Screen::Game,
+            first_year,
+            last_year,
+            random_date,
+            game: TrainingSession::new(Challenge::day(Method::ShakuntalaDevi, random_date)),
+            month_table,
+            year_table,
+            first_date,
+            second_date,
+            days_between: TrainingSession::new(Challenge::days_between(first_date, second_date)),
+            days_between_input: String::new(),
+            difficulty,
+            weekday_drill: weekday_drill_session(difficulty, first_year, last_year),
+            puzzle,
+            puzzle_session: TrainingSession::new(Challenge::puzzle(puzzle)),
+            puzzle_input: String::new(),
+            guided: GuidedSolve::new(shakuntala_devi_trainer::random_date(first_year, last_year)),
+            guided_input: String::new(),
+            blitz: None,
+            blitz_duration: DURATIONS[0],
+            blitz_best: practice::blitz_best(DURATIONS[0], first_year, last_year),
+            first_year_input: first_year.to_string(),
+            last_year_input: last_year.to_string(),
+            range_error: None,
+            statistics: stats::breakdowns(&practice.attempts),
+            practice,
+            adaptive,
+            hint,
+            settings,
+        }
+    }
+
+    //the screen and the year range are saved for the next launch
+    fn save_settings(&mut self) {
+        self.settings.screen = self.screen;
+        self.settings.first_year = self.first_year;
+        self.settings.last_year = self.last_year;
+        self.settings.save();
+    }
+
+    //the year range of the screen, the year table drill has its own
+    fn year_range(&self) -> (u32, u32) {
+        match self.screen {
+            Screen::TrainingYearTable => (
+                self.settings.year_table_first_year,
+                self.settings.year_table_last_year,
+            ),
+            _ => (self.first_year, self.last_year),
+        }
+    }
+
+    fn sync_year_inputs(&mut self) {
+        let (first_year, last_year) = self.year_range();
+        self.first_year_input = first_year.to_string();
+        self.last_year_input = last_year.to_string();
+        self.range_error = None;
+    }
+
+    //a range the method can't play is explained instead of being applied
+    //like the sliders the new range is used from the next game
+    fn set_year_range(&mut self, first_year: u32, last_year: u32) -> bool {
+        match shakuntala_devi_trainer::validate_year_range(
+            first_year,
+            last_year,
+            Method::ShakuntalaDevi,
+        ) {
+            Ok(()) => {
+                match self.screen {
+                    Screen::TrainingYearTable => {
+                        self.settings.year_table_first_year = first_year;
+                        self.settings.year_table_last_year = last_year;
+                    }
+                    _ => {
+                        self.first_year = first_year;
+                        self.last_year = last_year;
+                    }
+                }
+                if self.screen == Screen::Blitz {
+                    self.blitz_best =
+                        practice::blitz_best(self.blitz_duration, first_year, last_year);
+                }
+                self.sync_year_inputs();
+                true
+            }
+            Err(error) => {
+                self.range_error = Some(format!("Sorry, {}", error));
+                false
+            }
+        }
+    }
+
+    //the typed years are checked once they have 4 digits or on Enter
+    fn typed_year_range(&mut self, submit: bool) {
+        match (self.first_year_input.parse(), self.last_year_input.parse()) {
+            (Ok(first_year), Ok(last_year))
+                if submit
+                    || self.first_year_input.len() == 4 && self.last_year_input.len() == 4 =>
+            {
+                if self.set_year_range(first_year, last_year) {
+                    self.save_settings();
+                }
+            }
+            _ if submit => self.range_error = Some("Type the years with 4 digits".to_string()),
+            _ => self.range_error = None,
+        }
+    }
+
+    //the challenges tried and left for a new game are given up, the others were maybe never seen
+    fn give_up_started(&mut self) {
+        for session in [
+            &mut self.game,
+            &mut self.month_table,
+            &mut self.year_table,
+            &mut self.days_between,
+            &mut self.weekday_drill,
+            &mut self.puzzle_session,
+            &mut self.guided.session,
+        ] {
+            if session.is_started() {
+                self.practice.give_up(session);
+            }
+        }
+    }
+
+    //the score is kept once the time is up
+    fn end_blitz(&mut self) {
+        if let Some(blitz) = self.blitz.take_if(|blitz| blitz.is_over()) {
+            let new_best = practice::record_blitz(&blitz);
+            if new_best {
+                self.blitz_best = Some(blitz.score());
+            }
+            self.hint[Screen::Blitz] = format!(
+                "Time is up ! Score {}: {} found and {} wrong{}",
+                blitz.score(),
+                blitz.found(),
+                blitz.wrong(),
+                if new_best {
+                    ", new personal best !"
+                } else {
+                    ""
+                }
             );
-        (
-            Self {
-                screen: Screen::Game,
-                first_year: shakuntala_devi_trainer::DEFAULT_FIRST_YEAR,
-                last_year: shakuntala_devi_trainer::DEFAULT_LAST_YEAR,
-                random_date,
-                week_day: shakuntala_devi_answer,
-                game_answers: [false; 7],
-                month_table_answers: [false; 7],
-                year_table_answers: [false; 13],
-                tips,
-                hint: enum_map! {
-                    Screen::Game => "Guess the day!".to_string(),
-                    Screen::Solution => "".to_string(),
-                    Screen::TrainingMonthTable => "Which entry is the good one ?".to_string(),
-                    Screen::TrainingYearTable => "Which entry is the good one ?".to_string(),
-                },
-                start: web_time::Instant::now(),
+        }
+    }
+
+    //Tab and Ctrl with a number switch screens, the other keys answer like the buttons of the screen
+    fn key_message(&self, key: Key, modifiers: Modifiers) -> Option<Message> {
+        match key.as_ref() {
+            Key::Named(Named::Tab) => {
+                let step = if modifiers.shift() {
+                    Screen::LENGTH - 1
+                } else {
+                    1
+                };
+                Some(mode(Screen::from_usize(
+                    (self.screen.into_usize() + step) % Screen::LENGTH,
+                )))
+            }
+            Key::Named(Named::Enter) => match self.screen {
+                Screen::Statistics => None,
+                Screen::Blitz => Some(Message::StartBlitz),
+                _ => Some(Message::Reset),
             },
-            iced::Task::none(),
-        )
+            //Ctrl+0 is the tenth screen
+            Key::Character(c) if modifiers.command() => {
+                let index = (c.parse::<usize>().ok()? + 9) % 10;
+                (index < Screen::LENGTH).then(|| mode(Screen::from_usize(index)))
+            }
+            //weekdays are typed with the numbering of the settings
+            Key::Character(c) => {
+                let weekday = self.settings.numbering.parse(c).or(weekday_initial(c));
+                let entry = c.parse().ok().filter(|entry| (0..7).contains(entry));
+                match self.screen {
+                    Screen::Game => weekday.map(Message::GuessDay),
+                    Screen::WeekdayDrill => weekday.map(Message::GuessDrillDay),
+                    Screen::Blitz => weekday.map(Message::GuessBlitz),
+                    Screen::TrainingMonthTable => entry.map(Message::GuessMonthTable),
+                    Screen::TrainingYearTable => entry.map(Message::GuessYearTable),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn time(&self, session: &TrainingSession) -> Option<u64> {
+        self.settings
+            .show_timer
+            .then(|| session.elapsed().as_secs())
     }
 
     pub fn title(&self) -> String {
@@ -83,49 +496,42 @@ impl ShakuntalaDeviTrainer {
     }
 
     pub fn theme(&self) -> iced::Theme {
-        iced::Theme::Light
+        Palette::of(self.settings.theme).theme()
     }
 
     pub fn update(&mut self, message: Message) -> iced::Task<Message> {
         match message {
             Message::GuessDay(guess_day) => {
-                self.game_answers[usize::try_from(guess_day.number_from_monday() - 1)
-                    .ok()
-                    .unwrap()] = true;
-                let tries = self.game_answers.iter().filter(|&n| *n).count();
-                let result = if guess_day == self.week_day {
-                    self.game_answers = [true; 7];
-                    format!(
-                        "Congratulation ! You found {} after {} guess in {:#?}s",
-                        guess_day,
-                        tries,
-                        self.start.elapsed().as_secs()
-                    )
-                } else {
-                    match self.tips.0.get(tries - 1) {
-                        Some(tips) => format!("tips: {:#?}", tips),
-                        None => "Sorry, no more tips".to_string(),
-                    }
-                };
-                self.hint[self.screen] = result;
+                if let Some(feedback) = self.game.guess(Guess::Weekday(guess_day)) {
+                    self.hint[self.screen] = if feedback.correct {
+                        self.practice.record(&self.game);
+                        found_hint(
+                            format!("found {}", guess_day),
+                            feedback.tries,
+                            self.time(&self.game),
+                        )
+                    } else {
+                        mistake_hint(feedback)
+                    };
+                }
             }
 
+            //a blitz goes on with its own clock
             Message::Reset => {
-                let (random_date, shakuntala_devi_answer, tips) =
-                    shakuntala_devi_trainer::random_date_with_tips(self.first_year, self.last_year);
-                self.week_day = shakuntala_devi_answer;
-                self.random_date = random_date;
-                self.tips = tips;
-                self.hint = enum_map! {
-                    Screen::Game => "Guess the day!".to_string(),
-                    Screen::Solution => "".to_string(),
-                    Screen::TrainingMonthTable => "Which entry is the good one ?".to_string(),
-                    Screen::TrainingYearTable => "Which entry is the good one ?".to_string(),
+                self.give_up_started();
+                *self = Self {
+                    screen: self.screen,
+                    blitz: self.blitz.take(),
+                    blitz_duration: self.blitz_duration,
+                    ..Self::start(
+                        self.first_year,
+                        self.last_year,
+                        self.difficulty,
+                        self.settings.clone(),
+                        std::mem::take(&mut self.practice),
+                    )
                 };
-                self.game_answers = [false; 7];
-                self.month_table_answers = [false; 7];
-                self.year_table_answers = [false; 13];
-                self.start = web_time::Instant::now();
+                self.sync_year_inputs();
             }
 
             Message::TrainingMonthTableMode => {
@@ -136,72 +542,316 @@ impl ShakuntalaDeviTrainer {
                 self.screen = Screen::TrainingYearTable;
             }
 
+            //the countdown of the game only runs once it is shown
             Message::GameMode => {
                 self.screen = Screen::Game;
+                self.game.restart_clock();
             }
 
-            Message::SolutionMode => {
-                self.screen = Screen::Solution;
+            Message::DaysBetweenMode => {
+                self.screen = Screen::DaysBetween;
             }
 
-            Message::GuessMonthTable(guess) => {
-                self.month_table_answers[usize::try_from(guess).ok().unwrap()] = true;
-                if T2[self.random_date.month0() as usize] == guess {
-                    self.month_table_answers = [true; 7];
-                    self.hint[self.screen] =
-                        format!("Congratulation ! {} is the right answer", guess)
-                } else {
-                    self.hint[self.screen] = "Try again".to_string()
-                };
+            Message::WeekdayDrillMode => {
+                self.screen = Screen::WeekdayDrill;
             }
 
-            Message::GuessYearTable(guess) => {
-                self.year_table_answers[usize::try_from(guess).ok().unwrap()] = true;
-                let versatile_answer =
-                    shakuntala_devi_nearest_leap_year(self.random_date.year(), &mut None);
-                let answer = if versatile_answer > 12 {
-                    YEARS.get(&versatile_answer).unwrap()
-                } else {
-                    &versatile_answer
-                };
-                if *answer == guess {
-                    self.year_table_answers = [true; 13];
-                    self.hint[self.screen] =
-                        format!("Congratulation ! {} is the right answer", guess)
-                } else {
-                    self.hint[self.screen] = if versatile_answer > 12 {
+            Message::PuzzleMode => {
+                self.screen = Screen::Puzzle;
+            }
+
+            Message::GuidedMode => {
+                self.screen = Screen::Guided;
+            }
+
+            //the personal best follows the year range of the game
+            Message::BlitzMode => {
+                self.screen = Screen::Blitz;
+                self.blitz_best =
+                    practice::blitz_best(self.blitz_duration, self.first_year, self.last_year);
+            }
+
+            Message::BlitzDuration(duration) => {
+                self.blitz_duration = duration;
+                self.blitz_best = practice::blitz_best(duration, self.first_year, self.last_year);
+            }
+
+            Message::StartBlitz => {
+                self.blitz = Some(Blitz::new(
+                    self.blitz_duration,
+                    Method::ShakuntalaDevi,
+                    self.first_year,
+                    self.last_year,
+                ));
+                self.blitz_best =
+                    practice::blitz_best(self.blitz_duration, self.first_year, self.last_year);
+                self.hint[Screen::Blitz] = initial_hint()[Screen::Blitz].clone();
+            }
+
+            Message::GuessBlitz(weekday) => {
+                if let Some(session) = self.blitz.as_mut().and_then(|blitz| blitz.guess(weekday)) {
+                    self.practice.record(&session);
+                    self.hint[Screen::Blitz] = if session.outcome() == Some(Outcome::Found) {
                         format!(
-                            "Try again. Tips: no direct year table entry, nearest leap year {}",
-                            versatile_answer
+                            "Right, {} was a {}",
+                            session.challenge.challenge, session.challenge.answer
                         )
                     } else {
-                        "Try again, this is a direct year table entry".to_string()
+                        format!(
+                            "Wrong, {} was a {}",
+                            session.challenge.challenge, session.challenge.answer
+                        )
+                    };
+                }
+                self.end_blitz();
+            }
+
+            Message::GuidedInput(input) => {
+                if input.chars().all(|c| c.is_ascii_digit()) {
+                    self.guided_input = input;
+                }
+            }
+
+            //a step found is explained before the next one is asked
+            Message::GuessGuided => {
+                if let Ok(value) = self.guided_input.parse() {
+                    if let Some(feedback) = self.guided.answer(value) {
+                        self.hint[self.screen] = if feedback.correct {
+                            self.guided_input.clear();
+                            let explanation = &self.guided.found().last().unwrap().explanation;
+                            if self.guided.step().is_none() {
+                                self.practice.record(&self.guided.session);
+                                found_hint(
+                                    format!(
+                                        "found {} with {} wrong steps",
+                                        self.guided.session.challenge.answer,
+                                        self.guided.errors()
+                                    ),
+                                    self.guided.session.tries(),
+                                    self.time(&self.guided.session),
+                                )
+                            } else {
+                                format!("Right, {}", explanation)
+                            }
+                        } else {
+                            let explanation = feedback.explanation.unwrap_or_default();
+                            match possible_mistakes(&feedback.mistakes) {
+                                Some(mistakes) => {
+                                    format!("{}\nNot quite, {}", mistakes, explanation)
+                                }
+                                None => format!("Not quite, {}", explanation),
+                            }
+                        };
                     }
-                };
+                }
             }
 
-            Message::FirstYear(first_year) => {
-                if first_year < self.last_year {
-                    self.first_year = first_year;
+            //the history grows with every challenge found
+            Message::StatisticsMode => {
+                self.statistics = stats::breakdowns(&self.practice.attempts);
+                self.screen = Screen::Statistics;
+            }
+
+            Message::PuzzleInput(input) => {
+                self.puzzle_input = input;
+            }
+
+            Message::GuessPuzzle => match self.puzzle_session.challenge.parse(&self.puzzle_input) {
+                Some(guess) => {
+                    if let Some(feedback) = self.puzzle_session.guess(guess) {
+                        self.hint[self.screen] = if feedback.correct {
+                            self.practice.record(&self.puzzle_session);
+                            found_hint(
+                                "solved it".to_string(),
+                                feedback.tries,
+                                self.time(&self.puzzle_session),
+                            )
+                        } else {
+                            tip_hint(feedback.tip)
+                        };
+                    }
+                }
+                None => {
+                    self.hint[self.screen] = format!("Answer with {}", self.puzzle.answer_format())
+                }
+            },
+
+            Message::GuessDrillDay(guess_day) => {
+                if let Some(feedback) = self.weekday_drill.guess(Guess::Weekday(guess_day)) {
+                    self.hint[self.screen] = if feedback.correct {
+                        self.practice.record(&self.weekday_drill);
+                        format!(
+                            "Congratulation ! You found {} after {} guess",
+                            guess_day, feedback.tries
+                        )
+                    } else {
+                        tip_hint(feedback.tip)
+                    };
+                }
+            }
+
+            Message::DrillDifficulty(difficulty) => {
+                if self.weekday_drill.is_started() {
+                    self.practice.give_up(&mut self.weekday_drill);
+                }
+                self.difficulty = difficulty;
+                self.weekday_drill =
+                    weekday_drill_session(self.difficulty, self.first_year, self.last_year);
+                self.hint[Screen::WeekdayDrill] = initial_hint()[Screen::WeekdayDrill].clone();
+            }
+
+            Message::SolutionMode => {
+                self.screen = Screen::Solution;
+            }
+
+            Message::DaysBetweenInput(input) => {
+                if input.chars().all(|c| c.is_ascii_digit()) {
+                    self.days_between_input = input;
+                }
+            }
+
+            Message::GuessDaysBetween => {
+                if let Some(guess) = self.days_between.challenge.parse(&self.days_between_input) {
+                    if let Some(feedback) = self.days_between.guess(guess.clone()) {
+                        self.hint[self.screen] = if feedback.correct {
+                            self.practice.record(&self.days_between);
+                            found_hint(
+                                format!("found {}", guess),
+                                feedback.tries,
+                                self.time(&self.days_between),
+                            )
+                        } else {
+                            tip_hint(feedback.tip)
+                        };
+                    }
+                }
+            }
+
+            Message::GuessMonthTable(guess) => {
+                if let Some(hint) = check_table(&mut self.month_table, &mut self.practice, guess) {
+                    self.hint[self.screen] = hint;
                 }
             }
 
+            Message::GuessYearTable(guess) => {
+                if let Some(hint) = check_table(&mut self.year_table, &mut self.practice, guess) {
+                    self.hint[self.screen] = hint;
+                }
+            }
+
+            Message::FirstYear(first_year) => {
+                self.set_year_range(first_year, self.year_range().1);
+            }
+
             Message::LastYear(last_year) => {
-                if last_year > self.first_year {
-                    self.last_year = last_year;
+                self.set_year_range(self.year_range().0, last_year);
+            }
+
+            Message::FirstYearInput(input) => {
+                if input.len() <= 4 && input.chars().all(|c| c.is_ascii_digit()) {
+                    self.first_year_input = input;
+                    self.typed_year_range(false);
+                }
+            }
+
+            Message::LastYearInput(input) => {
+                if input.len() <= 4 && input.chars().all(|c| c.is_ascii_digit()) {
+                    self.last_year_input = input;
+                    self.typed_year_range(false);
+                }
+            }
+
+            Message::SubmitYears => self.typed_year_range(true),
+
+            Message::Preset(preset) => match preset.years(self.settings.birth_year) {
+                Ok((first_year, last_year)) => {
+                    if self.set_year_range(first_year, last_year) {
+                        self.save_settings();
+                    }
+                }
+                Err(error) => self.range_error = Some(error),
+            },
+
+            Message::SaveSettings => self.save_settings(),
+
+            Message::Countdown(countdown) => {
+                self.settings.countdown = countdown;
+                self.save_settings();
+            }
+
+            //the countdown ends the game like a solution asked, the answer is given
+            Message::Tick => {
+                if self.screen == Screen::Game
+                    && self.settings.countdown > 0
+                    && self.game.outcome().is_none()
+                    && self.game.elapsed().as_secs() >= self.settings.countdown
+                {
+                    self.game.time_out();
+                    self.practice.record(&self.game);
+                    self.hint[Screen::Game] =
+                        format!("Time is up ! It was {}", self.game.challenge.answer);
+                }
+                self.end_blitz();
+            }
+
+            Message::Key(key, modifiers) => {
+                if let Some(message) = self.key_message(key, modifiers) {
+                    return self.update(message);
                 }
             }
+
+            Message::Theme => {
+                self.settings.theme = self.settings.theme.next();
+                self.save_settings();
+            }
+
+            Message::Adaptive => {
+                self.settings.adaptive = !self.settings.adaptive;
+                self.save_settings();
+                return self.update(Message::Reset);
+            }
+        }
+        if self.screen != self.settings.screen {
+            self.sync_year_inputs();
+            self.save_settings();
         }
         iced::Task::none()
     }
 
+    //the game screen ticks while its date is played, a blitz until its time is up
+    //the keys typed in a text input are not shortcuts
+    pub fn subscription(&self) -> Subscription<Message> {
+        let keys = keyboard::listen().filter_map(|event| match event {
+            keyboard::Event::KeyPressed {
+                key,
+                modifiers,
+                repeat: false,
+                ..
+            } => Some(Message::Key(key, modifiers)),
+            _ => None,
+        });
+        if self.screen == Screen::Game
+            && self.game.outcome().is_none()
+            && (self.settings.show_timer || self.settings.countdown > 0)
+            || self.blitz.is_some()
+        {
+            Subscription::batch([
+                keys,
+                iced::time::every(Duration::from_millis(200)).map(|_| Message::Tick),
+            ])
+        } else {
+            keys
+        }
+    }
+
     pub fn view(&self) -> Element<'_, Message> {
+        let palette = Palette::of(self.settings.theme);
         let reset_button = column![button(
             text("Start new game")
                 .align_x(alignment::Horizontal::Center)
                 .size(14),
         )
-        .style(button_start)
+        .style(button_start(palette))
         .padding(8)
         .on_press(Message::Reset)]
         .padding(16);
@@ -213,10 +863,10 @@ impl ShakuntalaDeviTrainer {
         )
         .padding(8)
         .on_press(Message::GameMode)
-        .style(|theme, status| if self.screen == Screen::Game {
-            super::style::button_menu(theme, status)
+        .style(move |theme, status| if self.screen == Screen::Game {
+            super::style::button_menu(palette)(theme, status)
         } else {
-            super::style::button_menu_inactive(theme, status)
+            super::style::button_menu_inactive(palette)(theme, status)
         }),]
         .padding(16);
 
@@ -228,10 +878,10 @@ impl ShakuntalaDeviTrainer {
         .padding(8)
         .on_press(Message::TrainingMonthTableMode)
         .style(
-            |theme, status| if self.screen == Screen::TrainingMonthTable {
-                super::style::button_menu(theme, status)
+            move |theme, status| if self.screen == Screen::TrainingMonthTable {
+                super::style::button_menu(palette)(theme, status)
             } else {
-                super::style::button_menu_inactive(theme, status)
+                super::style::button_menu_inactive(palette)(theme, status)
             }
         ),]
         .padding(16);
@@ -244,10 +894,10 @@ impl ShakuntalaDeviTrainer {
         .padding(8)
         .on_press(Message::TrainingYearTableMode)
         .style(
-            |theme, status| if self.screen == Screen::TrainingYearTable {
-                super::style::button_menu(theme, status)
+            move |theme, status| if self.screen == Screen::TrainingYearTable {
+                super::style::button_menu(palette)(theme, status)
             } else {
-                super::style::button_menu_inactive(theme, status)
+                super::style::button_menu_inactive(palette)(theme, status)
             }
         ),]
         .padding(16);
@@ -259,25 +909,123 @@ impl ShakuntalaDeviTrainer {
         )
         .padding(8)
         .on_press(Message::SolutionMode)
-        .style(|theme, status| if self.screen == Screen::Solution {
-            super::style::button_menu(theme, status)
+        .style(move |theme, status| if self.screen == Screen::Solution {
+            super::style::button_menu(palette)(theme, status)
+        } else {
+            super::style::button_menu_inactive(palette)(theme, status)
+        }),]
+        .padding(16);
+
+        let menu_days_between = column![button(
+            text("DAYS BETWEEN")
+                .align_x(alignment::Horizontal::Center)
+                .size(14),
+        )
+        .padding(8)
+        .on_press(Message::DaysBetweenMode)
+        .style(move |theme, status| if self.screen == Screen::DaysBetween {
+            super::style::button_menu(palette)(theme, status)
+        } else {
+            super::style::button_menu_inactive(palette)(theme, status)
+        }),]
+        .padding(16);
+
+        let menu_weekday_drill = column![button(
+            text("WEEKDAY DRILL")
+                .align_x(alignment::Horizontal::Center)
+                .size(14),
+        )
+        .padding(8)
+        .on_press(Message::WeekdayDrillMode)
+        .style(
+            move |theme, status| if self.screen == Screen::WeekdayDrill {
+                super::style::button_menu(palette)(theme, status)
+            } else {
+                super::style::button_menu_inactive(palette)(theme, status)
+            }
+        ),]
+        .padding(16);
+
+        let menu_puzzle = column![button(
+            text("PUZZLE")
+                .align_x(alignment::Horizontal::Center)
+                .size(14),
+        )
+        .padding(8)
+        .on_press(Message::PuzzleMode)
+        .style(move |theme, status| if self.screen == Screen::Puzzle {
+            super::style::button_menu(palette)(theme, status)
+        } else {
+            super::style::button_menu_inactive(palette)(theme, status)
+        }),]
+        .padding(16);
+
+        let menu_guided = column![button(
+            text("GUIDED")
+                .align_x(alignment::Horizontal::Center)
+                .size(14),
+        )
+        .padding(8)
+        .on_press(Message::GuidedMode)
+        .style(move |theme, status| if self.screen == Screen::Guided {
+            super::style::button_menu(palette)(theme, status)
         } else {
-            super::style::button_menu_inactive(theme, status)
+            super::style::button_menu_inactive(palette)(theme, status)
         }),]
         .padding(16);
 
-        let menu = row![menu_game, menu_solution, menu_month_table, menu_year_table];
+        let menu_blitz = column![button(
+            text("BLITZ")
+                .align_x(alignment::Horizontal::Center)
+                .size(14),
+        )
+        .padding(8)
+        .on_press(Message::BlitzMode)
+        .style(move |theme, status| if self.screen == Screen::Blitz {
+            super::style::button_menu(palette)(theme, status)
+        } else {
+            super::style::button_menu_inactive(palette)(theme, status)
+        }),]
+        .padding(16);
 
-        let column_weekday = |label, weekday, already_pressed| {
+        let menu_statistics = column![button(
+            text("STATISTICS")
+                .align_x(alignment::Horizontal::Center)
+                .size(14),
+        )
+        .padding(8)
+        .on_press(Message::StatisticsMode)
+        .style(move |theme, status| if self.screen == Screen::Statistics {
+            super::style::button_menu(palette)(theme, status)
+        } else {
+            super::style::button_menu_inactive(palette)(theme, status)
+        }),]
+        .padding(16);
+
+        let menu = row![
+            menu_game,
+            menu_solution,
+            menu_month_table,
+            menu_year_table,
+            menu_days_between,
+            menu_weekday_drill,
+            menu_puzzle,
+            menu_guided,
+            menu_blitz,
+            menu_statistics
+        ]
+        .wrap();
+
+        let column_weekday = |label, weekday, already_pressed, on_guess: fn(Weekday) -> Message| {
             column![if already_pressed {
                 button(text(label).align_x(alignment::Horizontal::Center).size(14))
                     .padding(8)
-                    .style(button_day)
+                    .style(button_day(palette))
             } else {
                 button(text(label).align_x(alignment::Horizontal::Center).size(14))
                     .padding(8)
-                    .on_press(Message::GuessDay(weekday))
-                    .style(button_day)
+                    .on_press(on_guess(weekday))
+                    .style(button_day(palette))
             }]
             .padding(1)
         };
@@ -286,12 +1034,12 @@ impl ShakuntalaDeviTrainer {
             column![if already_pressed {
                 button(text(label).align_x(alignment::Horizontal::Center).size(14))
                     .padding(8)
-                    .style(button_day)
+                    .style(button_day(palette))
             } else {
                 button(text(label).align_x(alignment::Horizontal::Center).size(14))
                     .padding(8)
                     .on_press(Message::GuessMonthTable(weekday))
-                    .style(button_day)
+                    .style(button_day(palette))
             }]
             .padding(1)
         };
@@ -300,12 +1048,12 @@ impl ShakuntalaDeviTrainer {
             column![if already_pressed {
                 button(text(label).align_x(alignment::Horizontal::Center).size(14))
                     .padding(8)
-                    .style(button_day)
+                    .style(button_day(palette))
             } else {
                 button(text(label).align_x(alignment::Horizontal::Center).size(14))
                     .padding(8)
                     .on_press(Message::GuessYearTable(weekday))
-                    .style(button_day)
+                    .style(button_day(palette))
             }]
             .padding(1)
         };
@@ -318,93 +1066,495 @@ impl ShakuntalaDeviTrainer {
             let date = format!("{} {} {}", self.random_date.day(), month, year);
             match self.screen {
                 Screen::Game => column![text(date).size(40)].padding(8),
-                Screen::TrainingMonthTable => column![text(month).size(40)].padding(8),
-                Screen::TrainingYearTable => column![text(year).size(40)].padding(8),
+                Screen::TrainingMonthTable => {
+                    column![text(&self.month_table.challenge.challenge).size(40)].padding(8)
+                }
+                Screen::TrainingYearTable => {
+                    column![text(&self.year_table.challenge.challenge).size(40)].padding(8)
+                }
                 Screen::Solution => column![text(date).size(40)].padding(8),
+                Screen::WeekdayDrill => {
+                    column![text(&self.weekday_drill.challenge.challenge).size(40)].padding(8)
+                }
+                Screen::Puzzle => column![text(self.puzzle.to_string()).size(20)].padding(8),
+                Screen::Guided => {
+                    column![text(&self.guided.session.challenge.challenge).size(40)].padding(8)
+                }
+                Screen::Blitz => match &self.blitz {
+                    Some(blitz) => {
+                        column![text(&blitz.session.challenge.challenge).size(40)].padding(8)
+                    }
+                    None => {
+                        column![
+                            text(format!("Years {} to {}", self.first_year, self.last_year))
+                                .size(28)
+                        ]
+                        .padding(8)
+                    }
+                },
+                Screen::Statistics => {
+                    column![text(format!("{} attempts", self.practice.attempts.len())).size(28)]
+                        .padding(8)
+                }
+                Screen::DaysBetween => {
+                    let long_date = |dt: NaiveDate| {
+                        format!(
+                            "{} {} {}",
+                            dt.day(),
+                            Month::from_u32(dt.month()).unwrap().name(),
+                            dt.year()
+                        )
+                    };
+                    column![text(format!(
+                        "{} - {}",
+                        long_date(self.first_date),
+                        long_date(self.second_date)
+                    ))
+                    .size(28)]
+                    .padding(8)
+                }
             }
         };
 
-        let first_year = column![text(self.first_year.to_string()).size(10)];
+        let year_input = |label, input, on_input: fn(String) -> Message| {
+            text_input(label, input)
+                .on_input(on_input)
+                .on_submit(Message::SubmitYears)
+                .size(12)
+                .padding(2)
+                .width(Length::Fixed(60.0))
+        };
+
+        let range_error = || self.range_error.as_ref().map(|error| text(error).size(12));
+
+        //the other screens show their range with the typed years only
+        let compact_range = column![row![
+            text("Years").size(12),
+            year_input(
+                "first year",
+                &self.first_year_input,
+                Message::FirstYearInput
+            ),
+            text("to").size(12),
+            year_input("last year", &self.last_year_input, Message::LastYearInput),
+        ]
+        .spacing(6)
+        .align_y(Alignment::Center)]
+        .push(range_error())
+        .align_x(Alignment::Center);
 
-        let last_year = column![text(self.last_year.to_string()).size(10)];
+        let first_year = column![year_input(
+            "first year",
+            &self.first_year_input,
+            Message::FirstYearInput
+        )];
 
+        let last_year = column![year_input(
+            "last year",
+            &self.last_year_input,
+            Message::LastYearInput
+        )];
+
+        //the sliders only go through the years of the method
         let first_year_slider = column![Slider::new(
-            shakuntala_devi_trainer::MIN_YEAR..=shakuntala_devi_trainer::MAX_YEAR,
+            Method::ShakuntalaDevi.supported_years(),
             self.first_year,
             Message::FirstYear,
         )
-        .style(slider_style)]
+        .on_release(Message::SaveSettings)
+        .style(slider_style(palette))]
         .padding(0)
         .spacing(3);
 
         let last_year_slider = column![Slider::new(
-            shakuntala_devi_trainer::MIN_YEAR..=shakuntala_devi_trainer::MAX_YEAR,
+            Method::ShakuntalaDevi.supported_years(),
             self.last_year,
             Message::LastYear,
         )
-        .style(slider_style)]
+        .on_release(Message::SaveSettings)
+        .style(slider_style(palette))]
         .padding(0);
 
-        let weekday = row![
-            column_weekday("Monday", Weekday::Mon, self.game_answers[0],),
-            column_weekday("Tuesday", Weekday::Tue, self.game_answers[1],),
-            column_weekday("Wednesday", Weekday::Wed, self.game_answers[2],),
-            column_weekday("Thursday", Weekday::Thu, self.game_answers[3],),
-            column_weekday("Friday", Weekday::Fri, self.game_answers[4],),
-            column_weekday("Saturday", Weekday::Sat, self.game_answers[5],),
-            column_weekday("Sunday", Weekday::Sun, self.game_answers[6],)
-        ];
+        let weekday_row = |answers: &[bool; 7], on_guess: fn(Weekday) -> Message| {
+            row![
+                column_weekday("Monday", Weekday::Mon, answers[0], on_guess),
+                column_weekday("Tuesday", Weekday::Tue, answers[1], on_guess),
+                column_weekday("Wednesday", Weekday::Wed, answers[2], on_guess),
+                column_weekday("Thursday", Weekday::Thu, answers[3], on_guess),
+                column_weekday("Friday", Weekday::Fri, answers[4], on_guess),
+                column_weekday("Saturday", Weekday::Sat, answers[5], on_guess),
+                column_weekday("Sunday", Weekday::Sun, answers[6], on_guess)
+            ]
+        };
+
+        let weekday = weekday_row(&weekday_answers(&self.game), Message::GuessDay);
+
+        let column_difficulty = |label, difficulty| {
+            column![
+                button(text(label).align_x(alignment::Horizontal::Center).size(12))
+                    .padding(4)
+                    .on_press(Message::DrillDifficulty(difficulty))
+                    .style(move |theme, status| if self.difficulty == difficulty {
+                        super::style::button_menu(palette)(theme, status)
+                    } else {
+                        super::style::button_menu_inactive(palette)(theme, status)
+                    })
+            ]
+            .padding(1)
+        };
+
+        let weekday_drill = column![
+            row![
+                column_difficulty("EASY", Difficulty::Easy),
+                column_difficulty("MEDIUM", Difficulty::Medium),
+                column_difficulty("HARD", Difficulty::Hard),
+            ],
+            weekday_row(
+                &weekday_answers(&self.weekday_drill),
+                Message::GuessDrillDay
+            )
+        ]
+        .align_x(Alignment::Center);
+
+        let column_duration = |duration| {
+            column![button(
+                text(format!("{}s", duration))
+                    .align_x(alignment::Horizontal::Center)
+                    .size(12)
+            )
+            .padding(4)
+            .on_press(Message::BlitzDuration(duration))
+            .style(move |theme, status| if self.blitz_duration == duration {
+                super::style::button_menu(palette)(theme, status)
+            } else {
+                super::style::button_menu_inactive(palette)(theme, status)
+            })]
+            .padding(1)
+        };
+
+        let mut blitz_status = vec![];
+        if let Some(blitz) = &self.blitz {
+            blitz_status.push(format!("Time left {}s", blitz.remaining().as_secs()));
+            blitz_status.push(format!("Score {}", blitz.score()));
+        }
+        if let Some(best) = self.blitz_best {
+            blitz_status.push(format!("Personal best {}", best));
+        }
+
+        let blitz = column![
+            row(DURATIONS.map(|duration| column_duration(duration).into()))
+                .push(
+                    column![button(
+                        text("Start blitz")
+                            .align_x(alignment::Horizontal::Center)
+                            .size(14)
+                    )
+                    .style(button_start(palette))
+                    .padding(8)
+                    .on_press(Message::StartBlitz)]
+                    .padding(8)
+                )
+                .align_y(Alignment::Center),
+            text(blitz_status.join("    ")).size(14),
+            weekday_row(
+                &self
+                    .blitz
+                    .as_ref()
+                    .map_or([true; 7], |blitz| weekday_answers(&blitz.session)),
+                Message::GuessBlitz
+            )
+        ]
+        .align_x(Alignment::Center);
+
+        let month_table_answers = entry_answers(&self.month_table);
+        let year_table_answers = entry_answers(&self.year_table);
 
         let t3 = row![
-            column_t2("0", 0, self.month_table_answers[0],),
-            column_t2("1", 1, self.month_table_answers[1],),
-            column_t2("2", 2, self.month_table_answers[2],),
-            column_t2("3", 3, self.month_table_answers[3],),
-            column_t2("4", 4, self.month_table_answers[4],),
-            column_t2("5", 5, self.month_table_answers[5],),
-            column_t2("6", 6, self.month_table_answers[6],),
+            column_t2("0", 0, month_table_answers[0],),
+            column_t2("1", 1, month_table_answers[1],),
+            column_t2("2", 2, month_table_answers[2],),
+            column_t2("3", 3, month_table_answers[3],),
+            column_t2("4", 4, month_table_answers[4],),
+            column_t2("5", 5, month_table_answers[5],),
+            column_t2("6", 6, month_table_answers[6],),
         ];
 
         let t3_year = row![
-            column_t3("0", 0, self.year_table_answers[0],),
-            column_t3("1", 1, self.year_table_answers[1],),
-            column_t3("2", 2, self.year_table_answers[2],),
-            column_t3("3", 3, self.year_table_answers[3],),
-            column_t3("4", 4, self.year_table_answers[4],),
-            column_t3("5", 5, self.year_table_answers[5],),
-            column_t3("6", 6, self.year_table_answers[6],),
+            column_t3("0", 0, year_table_answers[0],),
+            column_t3("1", 1, year_table_answers[1],),
+            column_t3("2", 2, year_table_answers[2],),
+            column_t3("3", 3, year_table_answers[3],),
+            column_t3("4", 4, year_table_answers[4],),
+            column_t3("5", 5, year_table_answers[5],),
+            column_t3("6", 6, year_table_answers[6],),
         ];
 
-        let solution = row![text(format!("{}", self.tips))];
+        let solution = row![text(format!("{}", self.game.challenge.tips))];
+
+        let days_between = row![
+            text_input("number of days", &self.days_between_input)
+                .on_input(Message::DaysBetweenInput)
+                .on_submit(Message::GuessDaysBetween)
+                .size(14)
+                .padding(8)
+                .width(Length::Fixed(150.0)),
+            column![button(
+                text("Check")
+                    .align_x(alignment::Horizontal::Center)
+                    .size(14)
+            )
+            .padding(8)
+            .on_press(Message::GuessDaysBetween)
+            .style(button_day(palette))]
+            .padding(1)
+        ]
+        .align_y(Alignment::Center);
+
+        let puzzle = row![
+            text_input(self.puzzle.answer_format(), &self.puzzle_input)
+                .on_input(Message::PuzzleInput)
+                .on_submit(Message::GuessPuzzle)
+                .size(14)
+                .padding(8)
+                .width(Length::Fixed(350.0)),
+            column![button(
+                text("Check")
+                    .align_x(alignment::Horizontal::Center)
+                    .size(14)
+            )
+            .padding(8)
+            .on_press(Message::GuessPuzzle)
+            .style(button_day(palette))]
+            .padding(1)
+        ]
+        .align_y(Alignment::Center);
+
+        //the steps found stay on screen above the one asked
+        let guided = column(self.guided.found().iter().map(|step| {
+            row![
+                text(&step.question).size(14).width(Length::Fixed(400.0)),
+                text(step.answer.to_string()).size(14),
+            ]
+            .into()
+        }))
+        .push(self.guided.step().map(|step| {
+            row![
+                text(&step.question).size(14).width(Length::Fixed(400.0)),
+                text_input("number", &self.guided_input)
+                    .on_input(Message::GuidedInput)
+                    .on_submit(Message::GuessGuided)
+                    .size(14)
+                    .padding(8)
+                    .width(Length::Fixed(100.0)),
+                column![button(
+                    text("Check")
+                        .align_x(alignment::Horizontal::Center)
+                        .size(14)
+                )
+                .padding(8)
+                .on_press(Message::GuessGuided)
+                .style(button_day(palette))]
+                .padding(1)
+            ]
+            .align_y(Alignment::Center)
+        }))
+        .spacing(4);
 
-        let (main_screen, secondary_screen) = match self.screen {
-            Screen::Game => (random_date, weekday),
-            Screen::TrainingMonthTable => (random_date, t3),
-            Screen::TrainingYearTable => (random_date, t3_year),
-            Screen::Solution => (random_date, solution),
+        let statistics_line = |cells: [String; 4]| {
+            row![
+                text(cells[0].clone()).size(14).width(Length::Fixed(150.0)),
+                text(cells[1].clone()).size(14).width(Length::Fixed(90.0)),
+                text(cells[2].clone()).size(14).width(Length::Fixed(90.0)),
+                text(cells[3].clone()).size(14).width(Length::Fixed(110.0)),
+            ]
         };
 
-        let container_slider = Container::new(
+        let statistics = scrollable(
+            column(
+                self.statistics
+                    .iter()
+                    .filter(|breakdown| !breakdown.lines.is_empty())
+                    .map(|breakdown| {
+                        column![statistics_line([
+                            format!("PER {}", breakdown.by.replace('_', " ").to_uppercase()),
+                            "Attempts".to_string(),
+                            "Accuracy".to_string(),
+                            "Median time".to_string(),
+                        ])]
+                        .extend(breakdown.lines.iter().map(|line| {
+                            statistics_line([
+                                line.label.clone(),
+                                line.attempts.to_string(),
+                                format!("{:.0}%", 100.0 * line.accuracy),
+                                line.median_time
+                                    .map_or("-".to_string(), |time| format!("{:.1}s", time)),
+                            ])
+                            .into()
+                        }))
+                        .padding(8)
+                        .into()
+                    }),
+            )
+            .align_x(Alignment::Center)
+            .width(Length::Fill),
+        )
+        .height(Length::Fill);
+
+        let (main_screen, secondary_screen): (_, Element<'_, Message>) = match self.screen {
+            Screen::Game => (random_date, weekday.into()),
+            Screen::TrainingMonthTable => (random_date, t3.into()),
+            Screen::TrainingYearTable => (random_date, t3_year.into()),
+            Screen::Solution => (random_date, solution.into()),
+            Screen::DaysBetween => (random_date, days_between.into()),
+            Screen::WeekdayDrill => (random_date, weekday_drill.into()),
+            Screen::Puzzle => (random_date, puzzle.into()),
+            Screen::Guided => (random_date, guided.into()),
+            Screen::Blitz => (random_date, blitz.into()),
+            Screen::Statistics => (random_date, statistics.into()),
+        };
+
+        let adaptive_button = button(
+            text("ADAPTIVE")
+                .align_x(alignment::Horizontal::Center)
+                .size(12),
+        )
+        .padding(4)
+        .on_press(Message::Adaptive)
+        .style(move |theme, status| {
+            if self.settings.adaptive {
+                super::style::button_menu(palette)(theme, status)
+            } else {
+                super::style::button_menu_inactive(palette)(theme, status)
+            }
+        });
+
+        let year_range: Element<'_, Message> = if self.settings.adaptive {
+            let years = self.adaptive.years(Method::ShakuntalaDevi);
+            text(format!(
+                "Level {} of {}: years {} to {}",
+                self.adaptive.level(),
+                MAX_LEVEL,
+                years.start(),
+                years.end()
+            ))
+            .size(10)
+            .into()
+        } else {
+            let column_preset = |preset: Preset| {
+                column![button(
+                    text(preset.label())
+                        .align_x(alignment::Horizontal::Center)
+                        .size(10)
+                )
+                .padding(2)
+                .on_press(Message::Preset(preset))
+                .style(super::style::button_menu_inactive(palette))]
+                .padding(1)
+                .into()
+            };
             first_year_slider
                 .push(first_year)
                 .align_x(Alignment::Center)
                 .push(last_year_slider)
-                .push(last_year),
-        );
+                .push(last_year)
+                .push(row(Preset::ALL.map(column_preset)))
+                .push(range_error())
+                .into()
+        };
 
-        let game = Container::new(
-            main_screen
-                .align_x(Alignment::Center)
-                .push(reset_button)
-                .push(secondary_screen)
-                .push(result),
+        let column_countdown = |label, countdown| {
+            column![
+                button(text(label).align_x(alignment::Horizontal::Center).size(12))
+                    .padding(4)
+                    .on_press(Message::Countdown(countdown))
+                    .style(
+                        move |theme, status| if self.settings.countdown == countdown {
+                            super::style::button_menu(palette)(theme, status)
+                        } else {
+                            super::style::button_menu_inactive(palette)(theme, status)
+                        }
+                    )
+            ]
+            .padding(1)
+        };
+
+        //the button shows the theme in use, a press goes to the next one
+        let theme_button = button(
+            text(format!(
+                "THEME: {}",
+                Palette::of(self.settings.theme).name.to_uppercase()
+            ))
+            .align_x(alignment::Horizontal::Center)
+            .size(12),
+        )
+        .padding(4)
+        .on_press(Message::Theme)
+        .style(super::style::button_menu_inactive(palette));
+
+        let options = row![
+            column![theme_button].padding(1),
+            column![adaptive_button].padding(1),
+            column_countdown("NO COUNTDOWN", 0),
+            column_countdown("10s", 10),
+            column_countdown("20s", 20),
+            column_countdown("30s", 30),
+        ];
+
+        let elapsed = self.game.elapsed().as_secs();
+        let mut clock = vec![];
+        if self.settings.show_timer {
+            clock.push(format!("Time {}s", elapsed));
+        }
+        if self.settings.countdown > 0 {
+            clock.push(format!(
+                "Time left {}s",
+                self.settings.countdown.saturating_sub(elapsed)
+            ));
+        }
+
+        let container_slider = Container::new(
+            column![options, year_range, text(clock.join("    ")).size(14)]
+                .align_x(Alignment::Center),
         );
 
+        //the statistics have no challenge to play
+        let game = match self.screen {
+            Screen::Statistics => Container::new(
+                main_screen
+                    .align_x(Alignment::Center)
+                    .push(secondary_screen),
+            ),
+            //a blitz chains its dates without a new game
+            Screen::Blitz => Container::new(
+                main_screen
+                    .align_x(Alignment::Center)
+                    .push(secondary_screen)
+                    .push(result),
+            ),
+            _ => Container::new(
+                main_screen
+                    .align_x(Alignment::Center)
+                    .push(reset_button)
+                    .push(secondary_screen)
+                    .push(result),
+            ),
+        };
+
         let content = match self.screen {
             Screen::Game => column![menu, container_slider, game].align_x(Alignment::Center),
-            Screen::TrainingMonthTable => column![menu, game].align_x(Alignment::Center),
-            Screen::TrainingYearTable => column![menu, game].align_x(Alignment::Center),
+            Screen::TrainingMonthTable => {
+                column![menu, compact_range, game].align_x(Alignment::Center)
+            }
+            Screen::TrainingYearTable => {
+                column![menu, compact_range, game].align_x(Alignment::Center)
+            }
             Screen::Solution => column![menu, game].align_x(Alignment::Center),
+            Screen::DaysBetween => column![menu, compact_range, game].align_x(Alignment::Center),
+            Screen::WeekdayDrill => column![menu, compact_range, game].align_x(Alignment::Center),
+            Screen::Puzzle => column![menu, compact_range, game].align_x(Alignment::Center),
+            Screen::Guided => column![menu, compact_range, game].align_x(Alignment::Center),
+            Screen::Blitz => column![menu, compact_range, game].align_x(Alignment::Center),
+            Screen::Statistics => column![menu, game].align_x(Alignment::Center),
         }
         .spacing(3);
 
@@ -412,7 +1562,7 @@ impl ShakuntalaDeviTrainer {
             .width(Length::Fill)
             .height(Length::Fill)
             .center_x(Length::Fill)
-            .style(crate::gui::style::main_container)
+            .style(crate::gui::style::main_container(palette))
             .into()
     }
 }
@@ -1,10 +1,18 @@
 use enum_map::Enum;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default, Enum, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Enum, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum Screen {
     #[default]
     Game,
     Solution,
     TrainingMonthTable,
     TrainingYearTable,
+    DaysBetween,
+    WeekdayDrill,
+    Puzzle,
+    Guided,
+    Blitz,
+    Statistics,
 }
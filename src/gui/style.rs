@@ -1,5 +1,6 @@
 use iced::{
     border::Radius,
+    color,
     widget::{
         button, container,
         slider::{self},
@@ -7,14 +8,131 @@ use iced::{
     Border, Color, Theme,
 };
 
-pub fn main_container(_theme: &Theme) -> container::Style {
-    container::Style {
-        background: Some(Color::from_rgb8(0xFF, 0xFE, 0xF0).into()),
+use crate::settings;
+
+//every color of a theme of the settings
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    pub name: &'static str,
+    pub background: Color,
+    pub text: Color,
+    pub button_text: Color,
+    pub hovered_text: Color,
+    pub day: Color,
+    pub pressed: Color,
+    pub pressed_text: Color,
+    //the color-blind theme outlines the pressed buttons so their state does not rely on a hue
+    pub pressed_border: Option<Color>,
+    pub start: Color,
+    pub menu: Color,
+    pub rail: Color,
+    pub handle: Color,
+    pub hovered_handle: Color,
+}
+
+impl Palette {
+    pub const LIGHT: Palette = Palette {
+        name: "Light",
+        background: color!(0xFFFEF0),
+        text: Color::BLACK,
+        button_text: color!(0xEEEEEE),
+        hovered_text: Color::WHITE,
+        day: color!(0x1C6BDE),
+        pressed: color!(0x96ABE8),
+        pressed_text: color!(0xEEEEEE),
+        pressed_border: None,
+        start: color!(0x1CAB1C),
+        menu: color!(0x8700D9),
+        rail: color!(0xDADADA),
+        handle: color!(0x9C9C9C),
+        hovered_handle: color!(0xFCFCFC),
+    };
+
+    pub const DARK: Palette = Palette {
+        name: "Dark",
+        background: color!(0x1E1E24),
+        text: color!(0xE8E8E8),
+        button_text: color!(0xEEEEEE),
+        hovered_text: Color::WHITE,
+        day: color!(0x3D7EE0),
+        pressed: color!(0x2E3B55),
+        pressed_text: color!(0x9A9AA8),
+        pressed_border: None,
+        start: color!(0x2E9E3E),
+        menu: color!(0x9B4DE0),
+        rail: color!(0x55555C),
+        handle: color!(0x9C9C9C),
+        hovered_handle: color!(0xDADADA),
+    };
+
+    pub const HIGH_CONTRAST: Palette = Palette {
+        name: "High contrast",
+        background: Color::BLACK,
+        text: Color::WHITE,
+        button_text: Color::BLACK,
+        hovered_text: Color::BLACK,
+        day: color!(0xFFFF00),
+        pressed: color!(0x333333),
+        pressed_text: color!(0xAAAAAA),
+        pressed_border: Some(color!(0xAAAAAA)),
+        start: color!(0x00FF00),
+        menu: color!(0x00FFFF),
+        rail: Color::WHITE,
+        handle: color!(0xFFFF00),
+        hovered_handle: Color::WHITE,
+    };
+
+    //the Okabe-Ito colors, the pressed buttons are grey
+    pub const COLOR_BLIND: Palette = Palette {
+        name: "Color blind",
+        background: color!(0xFFFEF0),
+        text: Color::BLACK,
+        button_text: Color::WHITE,
+        hovered_text: Color::WHITE,
+        day: color!(0x0072B2),
+        pressed: color!(0xE6E6E6),
+        pressed_text: color!(0x555555),
+        pressed_border: Some(color!(0x555555)),
+        start: color!(0x009E73),
+        menu: color!(0xD55E00),
+        rail: color!(0xBBBBBB),
+        handle: color!(0x555555),
+        hovered_handle: color!(0x0072B2),
+    };
+
+    pub fn of(theme: settings::Theme) -> Palette {
+        match theme {
+            settings::Theme::Light => Palette::LIGHT,
+            settings::Theme::Dark => Palette::DARK,
+            settings::Theme::HighContrast => Palette::HIGH_CONTRAST,
+            settings::Theme::ColorBlind => Palette::COLOR_BLIND,
+        }
+    }
+
+    pub fn theme(self) -> Theme {
+        Theme::custom(
+            self.name,
+            iced::theme::Palette {
+                background: self.background,
+                text: self.text,
+                primary: self.day,
+                success: self.start,
+                ..iced::theme::Palette::LIGHT
+            },
+        )
+    }
+}
+
+//the style functions are given the palette of the theme of the settings
+pub fn main_container(palette: Palette) -> impl Fn(&Theme) -> container::Style {
+    move |_| container::Style {
+        background: Some(palette.background.into()),
+        text_color: Some(palette.text),
         ..Default::default()
     }
 }
 
-pub fn rounded(theme: &Theme, status: button::Status) -> button::Style {
+fn rounded(palette: Palette, theme: &Theme, status: button::Status) -> button::Style {
     let radius = Radius {
         top_left: 12.0,
         top_right: 12.0,
@@ -22,83 +140,93 @@ pub fn rounded(theme: &Theme, status: button::Status) -> button::Style {
         bottom_right: 12.0,
     };
     button::Style {
-        text_color: Color::from_rgb8(0xEE, 0xEE, 0xEE),
+        text_color: palette.button_text,
         border: Border {
             radius,
             width: 1.0,
-            color: Color::from_rgb(0.53, 0.0, 0.85),
+            color: palette.menu,
         },
         ..button::primary(theme, status)
     }
 }
 
-pub fn button_start(theme: &Theme, status: button::Status) -> button::Style {
-    match status {
+pub fn button_start(palette: Palette) -> impl Fn(&Theme, button::Status) -> button::Style {
+    move |theme, status| match status {
         iced::widget::button::Status::Hovered => button::Style {
-            background: Some(Color::from_rgb(0.11, 0.67, 0.11).into()),
-            text_color: Color::WHITE,
-            ..rounded(theme, status)
+            background: Some(palette.start.into()),
+            text_color: palette.hovered_text,
+            ..rounded(palette, theme, status)
         },
         _ => button::Style {
-            background: Some(Color::from_rgb(0.11, 0.67, 0.11).into()),
-            ..rounded(theme, status)
+            background: Some(palette.start.into()),
+            ..rounded(palette, theme, status)
         },
     }
 }
 
-pub fn button_day(theme: &Theme, status: button::Status) -> button::Style {
-    match status {
+pub fn button_day(palette: Palette) -> impl Fn(&Theme, button::Status) -> button::Style {
+    move |theme, status| match status {
         iced::widget::button::Status::Hovered => button::Style {
-            background: Some(Color::from_rgb(0.11, 0.42, 0.87).into()),
-            text_color: Color::WHITE,
-            ..rounded(theme, status)
-        },
-        iced::widget::button::Status::Disabled => button::Style {
-            background: Some(Color::from_rgb(00.59, 0.67, 0.91).into()),
-            ..rounded(theme, status)
+            background: Some(palette.day.into()),
+            text_color: palette.hovered_text,
+            ..rounded(palette, theme, status)
         },
+        iced::widget::button::Status::Disabled => {
+            let style = rounded(palette, theme, status);
+            button::Style {
+                background: Some(palette.pressed.into()),
+                text_color: palette.pressed_text,
+                border: Border {
+                    width: if palette.pressed_border.is_some() {
+                        2.0
+                    } else {
+                        style.border.width
+                    },
+                    color: palette.pressed_border.unwrap_or(style.border.color),
+                    ..style.border
+                },
+                ..style
+            }
+        }
         _ => button::Style {
-            background: Some(Color::from_rgb(0.11, 0.42, 0.87).into()),
-            ..rounded(theme, status)
+            background: Some(palette.day.into()),
+            ..rounded(palette, theme, status)
         },
     }
 }
 
-pub fn button_menu(theme: &Theme, status: button::Status) -> button::Style {
-    match status {
+pub fn button_menu(palette: Palette) -> impl Fn(&Theme, button::Status) -> button::Style {
+    move |theme, status| match status {
         iced::widget::button::Status::Hovered => button::Style {
-            background: Some(Color::from_rgb(0.53, 0.0, 0.85).into()),
-            text_color: Color::WHITE,
-            ..rounded(theme, status)
+            background: Some(palette.menu.into()),
+            text_color: palette.hovered_text,
+            ..rounded(palette, theme, status)
         },
         _ => button::Style {
-            background: Some(Color::from_rgb(0.53, 0.0, 0.85).into()),
-            ..rounded(theme, status)
+            background: Some(palette.menu.into()),
+            ..rounded(palette, theme, status)
         },
     }
 }
 
-pub fn button_menu_inactive(theme: &Theme, status: button::Status) -> button::Style {
-    button::Style {
+pub fn button_menu_inactive(palette: Palette) -> impl Fn(&Theme, button::Status) -> button::Style {
+    move |theme, status| button::Style {
         background: None,
-        text_color: Color::BLACK,
-        ..rounded(theme, status)
+        text_color: palette.text,
+        ..rounded(palette, theme, status)
     }
 }
 
-pub fn slider_style(_theme: &Theme, status: slider::Status) -> slider::Style {
-    match status {
+pub fn slider_style(palette: Palette) -> impl Fn(&Theme, slider::Status) -> slider::Style {
+    move |_, status| match status {
         iced::widget::slider::Status::Active => slider::Style {
             rail: slider::Rail {
-                backgrounds: (
-                    Color::from_rgba8(0xda, 0xda, 0xda, 1.0).into(),
-                    Color::from_rgba8(0xda, 0xda, 0xda, 1.0).into(),
-                ),
+                backgrounds: (palette.rail.into(), palette.rail.into()),
                 width: 2.0,
                 border: Border {
                     radius: 2.0.into(),
                     width: 0.0,
-                    color: Color::from_rgba8(0x0, 0x0, 0x0, 1.0),
+                    color: palette.text,
                 },
             },
             handle: slider::Handle {
@@ -106,17 +234,14 @@ pub fn slider_style(_theme: &Theme, status: slider::Status) -> slider::Style {
                     width: 8,
                     border_radius: 12.0.into(),
                 },
-                background: Color::from_rgba8(0x9c, 0x9c, 0x9c, 1.0).into(),
-                border_color: Color::from_rgba8(0x0, 0x0, 0x0, 1.0),
+                background: palette.handle.into(),
+                border_color: palette.text,
                 border_width: 0.0,
             },
         },
         iced::widget::slider::Status::Hovered => slider::Style {
             rail: slider::Rail {
-                backgrounds: (
-                    Color::from_rgba8(0xda, 0xda, 0xda, 1.0).into(),
-                    Color::from_rgba8(0xda, 0xda, 0xda, 1.0).into(),
-                ),
+                backgrounds: (palette.rail.into(), palette.rail.into()),
                 width: 2.0,
                 border: Border {
                     radius: 12.0.into(),
@@ -129,22 +254,19 @@ pub fn slider_style(_theme: &Theme, status: slider::Status) -> slider::Style {
                     width: 8,
                     border_radius: 12.0.into(),
                 },
-                background: Color::from_rgba8(0xfc, 0xfc, 0xfc, 1.0).into(),
-                border_color: Color::from_rgba8(0x0, 0x0, 0x0, 1.0),
+                background: palette.hovered_handle.into(),
+                border_color: palette.text,
                 border_width: 1.0,
             },
         },
         iced::widget::slider::Status::Dragged => slider::Style {
             rail: slider::Rail {
-                backgrounds: (
-                    Color::from_rgba8(0xda, 0xda, 0xda, 1.0).into(),
-                    Color::from_rgba8(0xda, 0xda, 0xda, 1.0).into(),
-                ),
+                backgrounds: (palette.rail.into(), palette.rail.into()),
                 width: 2.0,
                 border: Border {
                     radius: 2.0.into(),
                     width: 0.0,
-                    color: Color::from_rgba8(0x0, 0x0, 0x0, 1.0),
+                    color: palette.text,
                 },
             },
             handle: slider::Handle {
@@ -152,8 +274,8 @@ pub fn slider_style(_theme: &Theme, status: slider::Status) -> slider::Style {
                     width: 8,
                     border_radius: 12.0.into(),
                 },
-                background: Color::from_rgba8(0xda, 0xda, 0xda, 1.0).into(),
-                border_color: Color::from_rgba8(0x0, 0x0, 0x0, 1.0),
+                background: palette.rail.into(),
+                border_color: palette.text,
                 border_width: 1.0,
             },
         },
@@ -0,0 +1,242 @@
+use chrono::prelude::*;
+
+use crate::diagnosis::Mistake;
+use crate::session::{Challenge, Guess, TrainingSession};
+use crate::{table_leap_year, weekday_name, Numbering, T2, YEARS};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepKind {
+    Day,
+    MonthEntry,
+    PartialSum,
+    NearestLeapYear,
+    YearEntry,
+    YearsSince,
+    Result,
+}
+
+//one intermediate value of Shakuntala Devi's method and how it is found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub kind: StepKind,
+    pub question: String,
+    pub answer: i32,
+    pub explanation: String,
+}
+
+//the same computation as shakuntala_devi, the result is the weekday number from Sunday 0
+pub fn steps(dt: NaiveDate) -> Vec<Step> {
+    let day = dt.day() as i32;
+    let month = Month::try_from(dt.month() as u8).unwrap().name();
+    let month_entry = T2[dt.month0() as usize];
+    let partial_sum = (day % 7 + month_entry) % 7;
+    let (leap_year, direct_leap_year) = table_leap_year(dt.year());
+    let year_entry = YEARS[&leap_year];
+    let years_since = dt.year() - leap_year;
+    let correction = direct_leap_year && dt.month() <= 2;
+    let result = (partial_sum + year_entry + years_since - i32::from(correction)).rem_euclid(7);
+    let weekday = Numbering::Devi.parse(&result.to_string()).unwrap();
+    let step = |kind, question: String, answer, explanation: String| Step {
+        kind,
+        question,
+        answer,
+        explanation,
+    };
+    vec![
+        step(
+            StepKind::Day,
+            format!("What is the day {} mod 7 ?", day),
+            day % 7,
+            format!("{} mod 7 = {}", day, day % 7),
+        ),
+        step(
+            StepKind::MonthEntry,
+            format!("What is the month table entry of {} ?", month),
+            month_entry,
+            format!("the month table entry of {} is {}", month, month_entry),
+        ),
+        step(
+            StepKind::PartialSum,
+            "What is (day + month table entry) mod 7 ?".to_string(),
+            partial_sum,
+            format!("({} + {}) mod 7 = {}", day % 7, month_entry, partial_sum),
+        ),
+        step(
+            StepKind::NearestLeapYear,
+            format!(
+                "Which leap year of the year table is {} computed from ?",
+                dt.year()
+            ),
+            leap_year,
+            if direct_leap_year {
+                format!("{} is a leap year of the year table", leap_year)
+            } else {
+                format!(
+                    "the nearest leap year before {} is {}",
+                    dt.year(),
+                    leap_year
+                )
+            },
+        ),
+        step(
+            StepKind::YearEntry,
+            format!("What is the year table entry of {} ?", leap_year),
+            year_entry,
+            format!("the year table entry of {} is {}", leap_year, year_entry),
+        ),
+        step(
+            StepKind::YearsSince,
+            format!("How many years from {} to {} ?", leap_year, dt.year()),
+            years_since,
+            format!("{} - {} = {}", dt.year(), leap_year, years_since),
+        ),
+        step(
+            StepKind::Result,
+            "Which weekday number is it, from Sunday 0 ?".to_string(),
+            result,
+            format!(
+                "({} + {} + {}{}) mod 7 = {}, a {}",
+                partial_sum,
+                year_entry,
+                years_since,
+                if correction {
+                    " - 1 for January and February of a leap year"
+                } else {
+                    ""
+                },
+                result,
+                weekday_name(weekday)
+            ),
+        ),
+    ]
+}
+
+//a wrong value gets the explanation of the step, the likely mistakes only come with the last step
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepFeedback {
+    pub correct: bool,
+    pub explanation: Option<String>,
+    pub mistakes: Vec<Mistake>,
+}
+
+//a day challenge solved one step at a time, the last step is the guess of the session
+#[derive(Debug, Clone)]
+pub struct GuidedSolve {
+    pub session: TrainingSession,
+    pub steps: Vec<Step>,
+    current: usize,
+    errors: u32,
+}
+
+impl GuidedSolve {
+    pub fn new(dt: NaiveDate) -> GuidedSolve {
+        GuidedSolve {
+            session: TrainingSession::new(Challenge::guided(dt)),
+            steps: steps(dt),
+            current: 0,
+            errors: 0,
+        }
+    }
+
+    //None once the session is over
+    pub fn step(&self) -> Option<&Step> {
+        if self.session.outcome().is_some() {
+            return None;
+        }
+        self.steps.get(self.current)
+    }
+
+    //the steps already found, in order
+    pub fn found(&self) -> &[Step] {
+        &self.steps[..self.current]
+    }
+
+    //the wrong values of every step
+    pub fn errors(&self) -> u32 {
+        self.errors
+    }
+
+    pub fn answer(&mut self, value: i32) -> Option<StepFeedback> {
+        let step = self.step()?.clone();
+        let correct = value == step.answer;
+        let mut mistakes = vec![];
+        if step.kind == StepKind::Result {
+            let guess = Numbering::Devi
+                .parse(&value.to_string())
+                .map(Guess::Weekday)
+                .filter(|guess| self.session.can_guess(guess));
+            if let Some(guess) = guess {
+                mistakes = self.session.guess(guess)?.mistakes;
+            }
+        }
+        if correct {
+            self.current += 1;
+        } else {
+            self.errors += 1;
+        }
+        Some(StepFeedback {
+            correct,
+            explanation: (!correct).then_some(step.explanation),
+            mistakes,
+        })
+    }
+
+    //the explanation of the current step, counted as a hint of the session
+    pub fn hint(&mut self) -> Option<String> {
+        let explanation = self.step()?.explanation.clone();
+        self.session.hint();
+        Some(explanation)
+    }
+}
+
+#[test]
+fn steps_check() {
+    let dt = NaiveDate::from_ymd_opt(1980, 2, 1).unwrap();
+    let answers: Vec<i32> = steps(dt).iter().map(|step| step.answer).collect();
+    assert_eq!(answers, vec![1, 3, 4, 1980, 2, 0, 5]);
+    let dt = NaiveDate::from_ymd_opt(1700, 2, 1).unwrap();
+    let answers: Vec<i32> = steps(dt).iter().map(|step| step.answer).collect();
+    assert_eq!(answers[3..6], [1696, YEARS[&1696], 4]);
+    let mut dt = NaiveDate::from_ymd_opt(1584, 1, 1).unwrap();
+    while dt.year() < 2200 {
+        let result = steps(dt).last().unwrap().answer;
+        assert_eq!(
+            Numbering::Devi.parse(&result.to_string()),
+            Some(dt.weekday()),
+            "testing {}",
+            dt
+        );
+        dt += chrono::Duration::days(37);
+    }
+}
+
+#[test]
+fn guided_solve_check() {
+    use crate::session::Outcome;
+    let dt = NaiveDate::from_ymd_opt(1980, 2, 1).unwrap();
+    let mut solve = GuidedSolve::new(dt);
+    let feedback = solve.answer(2).unwrap();
+    assert!(!feedback.correct);
+    assert_eq!(feedback.explanation, Some("1 mod 7 = 1".to_string()));
+    for value in [1, 3, 4, 1980, 2, 0] {
+        assert!(solve.answer(value).unwrap().correct);
+    }
+    assert_eq!(solve.found().len(), 6);
+    assert_eq!(solve.hint(), Some(solve.steps[6].explanation.clone()));
+    let feedback = solve.answer(6).unwrap();
+    assert!(!feedback.correct);
+    assert_eq!(
+        feedback.mistakes,
+        vec![
+            Mistake::LeapYearCorrection { year: 1980 },
+            Mistake::MonthEntry { month: 2, entry: 3 }
+        ]
+    );
+    assert!(solve.answer(5).unwrap().correct);
+    assert_eq!(solve.step(), None);
+    assert_eq!(solve.answer(5), None);
+    assert_eq!(solve.errors(), 2);
+    assert_eq!(solve.session.outcome(), Some(Outcome::Found));
+    assert_eq!(solve.session.tries(), 2);
+    assert_eq!(solve.session.hints(), 1);
+}
@@ -0,0 +1,95 @@
+use chrono::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::session::{Mode, Outcome, TrainingSession};
+use crate::Method;
+
+//one challenge played to its end, the history keeps one attempt per line in JSON
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Attempt {
+    pub played_at: DateTime<Utc>,
+    pub mode: Mode,
+    pub method: Method,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<NaiveDate>,
+    pub challenge: String,
+    pub answer: String,
+    pub guesses: Vec<String>,
+    pub hints: u32,
+    //in seconds
+    pub time: f64,
+    pub result: Outcome,
+}
+
+impl Attempt {
+    //None while the session is not over
+    pub fn new(session: &TrainingSession) -> Option<Attempt> {
+        Some(Attempt {
+            played_at: Utc::now(),
+            mode: session.challenge.mode,
+            method: session.challenge.method,
+            date: session.challenge.date,
+            challenge: session.challenge.challenge.clone(),
+            answer: session.challenge.answer.clone(),
+            guesses: session
+                .guesses()
+                .iter()
+                .map(|guess| guess.to_string())
+                .collect(),
+            hints: session.hints(),
+            time: session.elapsed().as_secs_f64(),
+            result: session.outcome()?,
+        })
+    }
+
+    pub fn success(&self) -> bool {
+        self.result == Outcome::Found
+    }
+
+    pub fn found_at_first_guess(&self) -> bool {
+        self.success() && self.guesses.len() == 1
+    }
+
+    pub fn to_line(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+//the attempts of a JSON lines text and the errors of the lines that can't be read
+pub fn parse(text: &str) -> (Vec<Attempt>, Vec<String>) {
+    let mut attempts = vec![];
+    let mut errors = vec![];
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(line) {
+            Ok(attempt) => attempts.push(attempt),
+            Err(error) => errors.push(format!("line {}: {}", index + 1, error)),
+        }
+    }
+    (attempts, errors)
+}
+
+#[test]
+fn attempt_check() {
+    use crate::session::{Challenge, Guess};
+    let dt = NaiveDate::from_ymd_opt(1980, 2, 1).unwrap();
+    let mut session = TrainingSession::new(Challenge::day(Method::ShakuntalaDevi, dt));
+    assert_eq!(Attempt::new(&session), None);
+    session.hint();
+    session.guess(Guess::Weekday(Weekday::Mon));
+    session.guess(Guess::Weekday(Weekday::Fri));
+    let attempt = Attempt::new(&session).unwrap();
+    assert_eq!(attempt.mode, Mode::Day);
+    assert_eq!(attempt.date, Some(dt));
+    assert_eq!(attempt.guesses, vec!["Mon", "Fri"]);
+    assert_eq!(attempt.hints, 1);
+    assert!(attempt.success());
+
+    let text = format!("{}\n\nnot json\n{}\n", attempt.to_line(), attempt.to_line());
+    let (attempts, errors) = parse(&text);
+    assert_eq!(attempts, vec![attempt.clone(), attempt]);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].starts_with("line 3:"));
+}
@@ -1,18 +1,52 @@
 use chrono::prelude::*;
 use chrono::Duration;
 use num_traits::cast::FromPrimitive;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::cell::RefCell;
 use std::fmt;
+use std::ops::RangeInclusive;
 use std::sync::LazyLock;
 use std::{
     collections::{HashMap, VecDeque},
     convert::TryInto,
 };
 
+pub mod adaptive;
+pub mod blitz;
+pub mod diagnosis;
+pub mod guided;
+pub mod history;
+pub mod puzzle;
+pub mod repetition;
+pub mod session;
+pub mod stats;
+
 pub const MIN_YEAR: u32 = 1583;
 pub const MAX_YEAR: u32 = 2204;
 pub const DEFAULT_FIRST_YEAR: u32 = 1932;
 pub const DEFAULT_LAST_YEAR: u32 = 2032;
 
+thread_local! {
+    static RNG: RefCell<StdRng> = RefCell::new(StdRng::from_os_rng());
+}
+
+//makes every random challenge of the current thread reproducible
+pub fn seed(seed: u64) {
+    RNG.with(|rng| *rng.borrow_mut() = StdRng::seed_from_u64(seed));
+}
+
+pub(crate) fn random_range<T, R>(range: R) -> T
+where
+    T: rand::distr::uniform::SampleUniform,
+    R: rand::distr::uniform::SampleRange<T>,
+{
+    RNG.with(|rng| rng.borrow_mut().random_range(range))
+}
+
+pub(crate) fn random_bool(p: f64) -> bool {
+    RNG.with(|rng| rng.borrow_mut().random_bool(p))
+}
+
 pub static YEARS: LazyLock<HashMap<i32, i32>> = LazyLock::new(|| {
     const T3: [i32; 7] = [0, 5, 3, 1, 6, 4, 2];
     let mut years = HashMap::new();
@@ -52,12 +86,88 @@ pub static YEARS: LazyLock<HashMap<i32, i32>> = LazyLock::new(|| {
 
 pub const T2: [i32; 12] = [0, 3, 3, 6, 1, 4, 6, 2, 5, 0, 3, 5];
 
+//what the year table entries of a century start from, 1900 for the 1900s
+pub fn century_offset(century: i32) -> i32 {
+    [0, 6, 4, 2][(century - 15).rem_euclid(4) as usize]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableDrill {
+    Month,
+    Year,
+    Century,
+}
+
+impl TableDrill {
+    pub fn question(self, dt: NaiveDate) -> String {
+        match self {
+            TableDrill::Month => Month::from_u32(dt.month()).unwrap().name().to_string(),
+            TableDrill::Year => dt.year().to_string(),
+            TableDrill::Century => format!("{}s", dt.year() / 100 * 100),
+        }
+    }
+
+    //the year table entry is the one of the nearest leap year when there is no direct entry
+    pub fn answer(self, dt: NaiveDate) -> i32 {
+        match self {
+            TableDrill::Month => T2[dt.month0() as usize],
+            TableDrill::Year => {
+                let versatile_answer = shakuntala_devi_nearest_leap_year(dt.year(), &mut None);
+                if versatile_answer > 12 {
+                    *YEARS.get(&versatile_answer).unwrap()
+                } else {
+                    versatile_answer
+                }
+            }
+            TableDrill::Century => century_offset(dt.year() / 100),
+        }
+    }
+
+    pub fn check(self, dt: NaiveDate, guess: i32) -> bool {
+        self.answer(dt) == guess
+    }
+
+    pub fn tips(self, dt: NaiveDate) -> Tips {
+        let mut v: Tips = Tips(VecDeque::new());
+        if self == TableDrill::Century {
+            v.0.push_back(
+                "the offsets are 6, 4, 2 and 0 from the 1600s to the 1900s then start again"
+                    .to_string(),
+            );
+        }
+        if self == TableDrill::Year {
+            let versatile_answer = shakuntala_devi_nearest_leap_year(dt.year(), &mut None);
+            v.0.push_back(if versatile_answer > 12 {
+                format!(
+                    "no direct year table entry, nearest leap year {}",
+                    versatile_answer
+                )
+            } else {
+                "this is a direct year table entry".to_string()
+            });
+        }
+        v
+    }
+}
+
 //https://stackoverflow.com/questions/725098/leap-year-calculation
 //https://en.wikipedia.org/wiki/Leap_year#Algorithm
-fn is_leap_year(y: i32) -> bool {
+pub(crate) fn is_leap_year(y: i32) -> bool {
     (y % 4 == 0) && (y % 100 != 0) || (y % 400 == 0)
 }
 
+//the leap year whose year table entry Shakuntala Devi's method starts from, true when it is the year itself
+pub(crate) fn table_leap_year(year: i32) -> (i32, bool) {
+    if YEARS.contains_key(&year) && is_leap_year(year) {
+        return (year, true);
+    }
+    let mut leap_year = year - 1;
+    while !is_leap_year(leap_year) {
+        leap_year -= 1;
+    }
+    (leap_year, false)
+}
+
 //https://stackoverflow.com/questions/6385190/correctness-of-sakamotos-algorithm-to-find-the-day-of-week
 pub fn tomohiko_sakamoto(dt: NaiveDate) -> Weekday {
     const DAYS: [i32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
@@ -203,6 +313,103 @@ pub fn svm_86(dt: NaiveDate) -> Weekday {
     Weekday::from_u32(distance % 7).unwrap()
 }
 
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+//same day and month n years later, 29 February falls back to 1 March on common years
+fn anniversary(dt: NaiveDate, years: i32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(dt.year() + years, dt.month(), dt.day())
+        .unwrap_or_else(|| NaiveDate::from_ymd_opt(dt.year() + years, 3, 1).unwrap())
+}
+
+pub fn days_between(from: NaiveDate, to: NaiveDate) -> (i64, Tips) {
+    let mut v: Tips = Tips(VecDeque::new());
+    let (first, last) = if from <= to { (from, to) } else { (to, from) };
+    let distance = i64::from(svm_86_distance(last)) - i64::from(svm_86_distance(first));
+
+    let mut full_years = 0;
+    while anniversary(first, full_years + 1) <= last {
+        full_years += 1;
+    }
+    let middle = anniversary(first, full_years);
+    let leap_days = (first.year()..=middle.year())
+        .filter_map(|year| NaiveDate::from_ymd_opt(year, 2, 29))
+        .filter(|leap_day| (first..middle).contains(leap_day))
+        .count() as i64;
+    v.0.push_back(format!(
+        "{} full years from {} to {}: {} x 365 = {}",
+        full_years,
+        first,
+        middle,
+        full_years,
+        full_years * 365
+    ));
+    v.0.push_back(format!("leap days crossed in those years {}", leap_days));
+
+    let mut months = 0;
+    if (middle.year(), middle.month()) == (last.year(), last.month()) {
+        months = i64::from(last.day() - middle.day());
+        v.0.push_back(format!(
+            "{} - {} = {} days left in {}",
+            last.day(),
+            middle.day(),
+            months,
+            Month::from_u32(last.month()).unwrap().name()
+        ));
+    } else {
+        let (mut year, mut month) = (middle.year(), middle.month());
+        let length = days_in_month(year, month);
+        months += i64::from(length - middle.day());
+        v.0.push_back(format!(
+            "{} - {} = {} days left in {} {}",
+            length,
+            middle.day(),
+            length - middle.day(),
+            Month::from_u32(month).unwrap().name(),
+            year
+        ));
+        loop {
+            (year, month) = if month == 12 {
+                (year + 1, 1)
+            } else {
+                (year, month + 1)
+            };
+            if (year, month) == (last.year(), last.month()) {
+                break;
+            }
+            let length = days_in_month(year, month);
+            months += i64::from(length);
+            v.0.push_back(format!(
+                "{} {} has {} days",
+                Month::from_u32(month).unwrap().name(),
+                year,
+                length
+            ));
+        }
+        months += i64::from(last.day());
+        v.0.push_back(format!(
+            "{} days into {} {}",
+            last.day(),
+            Month::from_u32(last.month()).unwrap().name(),
+            last.year()
+        ));
+    }
+    v.0.push_back(format!(
+        "{} + {} + {} = {} days",
+        full_years * 365,
+        leap_days,
+        months,
+        full_years as i64 * 365 + leap_days + months
+    ));
+    (distance, v)
+}
+
 pub const DOOMSDAY_COMMON_YEAR: [i32; 12] = [3, 28, 7, 4, 9, 6, 11, 8, 5, 10, 7, 12];
 pub const DOOMSDAY_LEAP_YEAR: [i32; 12] = [4, 29, 7, 4, 9, 6, 11, 8, 5, 10, 7, 12];
 
@@ -243,6 +450,130 @@ pub fn conway_doomsday(dt: NaiveDate) -> Weekday {
     Weekday::from_u32(result).unwrap().pred()
 }
 
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    clap::ValueEnum,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Method {
+    #[default]
+    ShakuntalaDevi,
+    TomohikoSakamoto,
+    Zeller,
+    #[value(name = "st-mag-53")]
+    #[serde(rename = "st-mag-53")]
+    StMag53,
+    #[value(name = "svm-86")]
+    #[serde(rename = "svm-86")]
+    Svm86,
+    ConwayDoomsday,
+}
+
+impl Method {
+    pub fn name(self) -> &'static str {
+        match self {
+            Method::ShakuntalaDevi => "Shakuntala Devi",
+            Method::TomohikoSakamoto => "Tomohiko Sakamoto",
+            Method::Zeller => "Zeller",
+            Method::StMag53 => "St Mag 53",
+            Method::Svm86 => "SVM 86",
+            Method::ConwayDoomsday => "Conway doomsday",
+        }
+    }
+
+    pub fn supported_years(self) -> RangeInclusive<u32> {
+        match self {
+            Method::ShakuntalaDevi => 1584..=2203,
+            Method::TomohikoSakamoto => 1583..=9999,
+            Method::Zeller => 1583..=9999,
+            Method::StMag53 => 1601..=1999,
+            Method::Svm86 => 1584..=9999,
+            Method::ConwayDoomsday => 1800..=2199,
+        }
+    }
+
+    pub fn weekday(self, dt: NaiveDate) -> Weekday {
+        self.solve(dt).0
+    }
+
+    //only Shakuntala Devi's method gives tips
+    pub fn solve(self, dt: NaiveDate) -> (Weekday, Tips) {
+        let no_tips = || Tips(VecDeque::new());
+        match self {
+            Method::ShakuntalaDevi => shakuntala_devi(dt),
+            Method::TomohikoSakamoto => (tomohiko_sakamoto(dt), no_tips()),
+            Method::Zeller => (zeller(dt), no_tips()),
+            Method::StMag53 => (st_mag_53(dt), no_tips()),
+            Method::Svm86 => (svm_86(dt), no_tips()),
+            Method::ConwayDoomsday => (conway_doomsday(dt), no_tips()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YearRangeError {
+    OutOfRange(u32),
+    Inverted(u32, u32),
+    Unsupported(Method, u32),
+}
+
+impl fmt::Display for YearRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            YearRangeError::OutOfRange(year) => write!(
+                f,
+                "year {} is out of range, years go from {} to {}",
+                year, MIN_YEAR, MAX_YEAR
+            ),
+            YearRangeError::Inverted(first_year, last_year) => write!(
+                f,
+                "first year {} must be before last year {}",
+                first_year, last_year
+            ),
+            YearRangeError::Unsupported(method, year) => write!(
+                f,
+                "year {} is not supported, {} only works from {} to {}",
+                year,
+                method.name(),
+                method.supported_years().start(),
+                method.supported_years().end()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for YearRangeError {}
+
+pub fn validate_year(year: u32, method: Method) -> Result<(), YearRangeError> {
+    if !(MIN_YEAR..=MAX_YEAR).contains(&year) {
+        Err(YearRangeError::OutOfRange(year))
+    } else if !method.supported_years().contains(&year) {
+        Err(YearRangeError::Unsupported(method, year))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn validate_year_range(
+    first_year: u32,
+    last_year: u32,
+    method: Method,
+) -> Result<(), YearRangeError> {
+    validate_year(first_year, method)?;
+    validate_year(last_year, method)?;
+    if first_year >= last_year {
+        return Err(YearRangeError::Inverted(first_year, last_year));
+    }
+    Ok(())
+}
+
 pub fn random_date(from_year: u32, to_year: u32) -> NaiveDate {
     let start = NaiveDate::from_ymd_opt(from_year.try_into().unwrap(), 1, 1)
         .unwrap()
@@ -250,7 +581,7 @@ pub fn random_date(from_year: u32, to_year: u32) -> NaiveDate {
     let end = NaiveDate::from_ymd_opt(to_year.try_into().unwrap(), 1, 1)
         .unwrap()
         .num_days_from_ce();
-    let days = rand::random_range(1..end - start);
+    let days = random_range(1..end - start);
     let dt = NaiveDate::from_ymd_opt(from_year.try_into().unwrap(), 1, 7).unwrap();
     dt + Duration::days(days as i64)
 }
@@ -274,6 +605,166 @@ pub fn random_date_with_tips(from_year: u32, to_year: u32) -> (NaiveDate, Weekda
     (random_date, shakuntala_devi_answer, tips)
 }
 
+pub fn random_date_pair_with_tips(
+    from_year: u32,
+    to_year: u32,
+) -> (NaiveDate, NaiveDate, i64, Tips) {
+    let first = random_date(from_year, to_year);
+    let second = random_date(from_year, to_year);
+    let (first, second) = if first <= second {
+        (first, second)
+    } else {
+        (second, first)
+    };
+    let (days, tips) = days_between(first, second);
+    (first, second, days, tips)
+}
+
+pub fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+//how the weekdays are written as numbers in the answers
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    clap::ValueEnum,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Numbering {
+    //Shakuntala Devi's book: Sunday 0 to Saturday 6
+    #[default]
+    Devi,
+    //ISO 8601: Monday 1 to Sunday 7
+    Iso,
+}
+
+impl Numbering {
+    pub fn number(self, weekday: Weekday) -> u32 {
+        match self {
+            Numbering::Devi => weekday.num_days_from_sunday(),
+            Numbering::Iso => weekday.number_from_monday(),
+        }
+    }
+
+    pub fn parse(self, input: &str) -> Option<Weekday> {
+        let number: u32 = input.parse().ok()?;
+        match self {
+            Numbering::Devi if number < 7 => Some(Weekday::from_u32(number).unwrap().pred()),
+            Numbering::Iso if (1..=7).contains(&number) => Weekday::from_u32(number - 1),
+            _ => None,
+        }
+    }
+}
+
+//Shakuntala Devi numbering: Sunday 0 to Saturday 6
+pub fn weekday_offset(from: Weekday, days: i64) -> (Weekday, Tips) {
+    let mut v: Tips = Tips(VecDeque::new());
+    let start = from.num_days_from_sunday() as i64;
+    let offset = days.rem_euclid(7);
+    v.0.push_back(format!("{} is {}", weekday_name(from), start));
+    v.0.push_back(format!("{} mod 7 = {}", days, offset));
+    let result = Weekday::from_i64((start + offset) % 7).unwrap().pred();
+    v.0.push_back(format!(
+        "({} + {}) mod 7 = {} that is {}",
+        start,
+        offset,
+        (start + offset) % 7,
+        weekday_name(result)
+    ));
+    (result, v)
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Difficulty {
+    #[default]
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn max_offset(self) -> i64 {
+        match self {
+            Difficulty::Easy => 14,
+            Difficulty::Medium => 100,
+            Difficulty::Hard => 1000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum DrillStart {
+    Weekday(Weekday),
+    Date(NaiveDate),
+}
+
+#[derive(Debug, Clone)]
+pub struct WeekdayDrill {
+    pub start: DrillStart,
+    pub offset: i64,
+    pub answer: Weekday,
+    pub tips: Tips,
+}
+
+impl fmt::Display for WeekdayDrill {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.start {
+            DrillStart::Weekday(weekday) => write!(f, "{}", weekday_name(weekday))?,
+            DrillStart::Date(dt) => write!(f, "{}", dt)?,
+        }
+        if self.offset < 0 {
+            write!(f, " - {} days", -self.offset)
+        } else {
+            write!(f, " + {} days", self.offset)
+        }
+    }
+}
+
+//easy drills start from a weekday, harder ones may start from a date whose weekday must be found first
+pub fn random_weekday_drill(difficulty: Difficulty, from_year: u32, to_year: u32) -> WeekdayDrill {
+    let max_offset = difficulty.max_offset();
+    let mut offset = random_range(1..=max_offset);
+    if difficulty != Difficulty::Easy && random_bool(0.5) {
+        offset = -offset;
+    }
+    if difficulty == Difficulty::Easy || random_bool(0.5) {
+        let weekday = Weekday::from_u32(random_range(0..7)).unwrap();
+        let (answer, tips) = weekday_offset(weekday, offset);
+        WeekdayDrill {
+            start: DrillStart::Weekday(weekday),
+            offset,
+            answer,
+            tips,
+        }
+    } else {
+        let dt = random_date(from_year, to_year);
+        let (weekday, mut tips) = shakuntala_devi(dt);
+        let (answer, offset_tips) = weekday_offset(weekday, offset);
+        tips.0.extend(offset_tips.0);
+        WeekdayDrill {
+            start: DrillStart::Date(dt),
+            offset,
+            answer,
+            tips,
+        }
+    }
+}
+
 #[test]
 fn tomohiko_sakamoto_check() {
     let calendar = NaiveDate::from_ymd_opt(1583, 1, 1).unwrap().iter_days();
@@ -374,6 +865,7 @@ fn leap_year_check() {
 }
 
 #[test]
+#[allow(clippy::partialeq_to_none)]
 fn leap_year_reverse_check() {
     for year in 1853..2204 {
         if is_leap_year(year) {
@@ -381,3 +873,184 @@ fn leap_year_reverse_check() {
         };
     }
 }
+
+#[test]
+fn days_between_unit_check() {
+    let from = NaiveDate::from_ymd_opt(1980, 3, 10).unwrap();
+    let to = NaiveDate::from_ymd_opt(1983, 6, 25).unwrap();
+    let (days, tips) = days_between(from, to);
+    println!("{}", tips);
+    assert_eq!(days, (to - from).num_days());
+    assert_eq!(days_between(to, from).0, days);
+    assert_eq!(
+        tips.0.back().unwrap(),
+        &format!("1095 + 0 + 107 = {} days", days)
+    );
+}
+
+#[test]
+fn days_between_check() {
+    let first = NaiveDate::from_ymd_opt(1996, 2, 29).unwrap();
+    let calendar = NaiveDate::from_ymd_opt(1583, 1, 1).unwrap().iter_days();
+    for dt in calendar.step_by(13) {
+        let (days, tips) = days_between(first, dt);
+        assert_eq!(days, (dt - first).num_days().abs(), "testing {}", dt);
+        assert!(
+            tips.0
+                .back()
+                .unwrap()
+                .ends_with(&format!("= {} days", days)),
+            "testing {}",
+            dt
+        );
+        if dt.year() == 2204 {
+            break;
+        };
+    }
+}
+
+#[test]
+fn weekday_offset_unit_check() {
+    assert_eq!(weekday_offset(Weekday::Tue, 45).0, Weekday::Fri);
+    assert_eq!(weekday_offset(Weekday::Tue, -45).0, Weekday::Sat);
+    assert_eq!(weekday_offset(Weekday::Sun, 0).0, Weekday::Sun);
+}
+
+#[test]
+fn weekday_offset_check() {
+    let dt = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
+    for days in -1000..1000 {
+        assert_eq!(
+            weekday_offset(dt.weekday(), days).0,
+            (dt + Duration::days(days)).weekday(),
+            "testing {}",
+            days
+        );
+    }
+}
+
+#[test]
+fn random_weekday_drill_check() {
+    for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+        for _ in 0..100 {
+            let drill = random_weekday_drill(difficulty, DEFAULT_FIRST_YEAR, DEFAULT_LAST_YEAR);
+            assert!(drill.offset.abs() <= difficulty.max_offset());
+            let expected = match drill.start {
+                DrillStart::Weekday(weekday) => weekday_offset(weekday, drill.offset).0,
+                DrillStart::Date(dt) => (dt + Duration::days(drill.offset)).weekday(),
+            };
+            assert_eq!(drill.answer, expected, "testing {}", drill);
+        }
+    }
+}
+
+#[test]
+fn seed_check() {
+    seed(42);
+    let first = random_date_with_tips(DEFAULT_FIRST_YEAR, DEFAULT_LAST_YEAR).0;
+    seed(42);
+    let second = random_date_with_tips(DEFAULT_FIRST_YEAR, DEFAULT_LAST_YEAR).0;
+    assert_eq!(first, second);
+}
+
+#[test]
+fn method_unit_check() {
+    let dt = NaiveDate::from_ymd_opt(1980, 2, 1).unwrap();
+    for method in [
+        Method::ShakuntalaDevi,
+        Method::TomohikoSakamoto,
+        Method::Zeller,
+        Method::StMag53,
+        Method::Svm86,
+        Method::ConwayDoomsday,
+    ] {
+        assert_eq!(
+            method.weekday(dt),
+            dt.weekday(),
+            "testing {}",
+            method.name()
+        );
+    }
+    assert!(!Method::ShakuntalaDevi.solve(dt).1 .0.is_empty());
+}
+
+#[test]
+fn method_supported_years_check() {
+    for method in [Method::StMag53, Method::ConwayDoomsday] {
+        let years = method.supported_years();
+        let first = NaiveDate::from_ymd_opt(*years.start() as i32, 1, 1).unwrap();
+        for dt in first.iter_days() {
+            if dt.year() as u32 > *years.end() {
+                break;
+            };
+            assert_eq!(method.weekday(dt), dt.weekday(), "testing {}", dt);
+        }
+    }
+}
+
+#[test]
+fn validate_year_range_unit_check() {
+    assert!(validate_year_range(DEFAULT_FIRST_YEAR, DEFAULT_LAST_YEAR, Method::default()).is_ok());
+    assert_eq!(
+        validate_year_range(1500, 2000, Method::default()),
+        Err(YearRangeError::OutOfRange(1500))
+    );
+    assert_eq!(
+        validate_year_range(2000, 1990, Method::default()),
+        Err(YearRangeError::Inverted(2000, 1990))
+    );
+    assert_eq!(
+        validate_year_range(1700, 2000, Method::ConwayDoomsday),
+        Err(YearRangeError::Unsupported(Method::ConwayDoomsday, 1700))
+    );
+}
+
+#[test]
+fn table_drill_unit_check() {
+    let dt = NaiveDate::from_ymd_opt(1980, 2, 1).unwrap();
+    assert!(TableDrill::Month.check(dt, 3));
+    assert!(TableDrill::Year.check(dt, 2));
+    assert!(TableDrill::Month.tips(dt).0.is_empty());
+    let dt = NaiveDate::from_ymd_opt(1983, 2, 1).unwrap();
+    assert!(TableDrill::Year.check(dt, 2));
+    assert_eq!(
+        TableDrill::Year.tips(dt).0[0],
+        "no direct year table entry, nearest leap year 1980"
+    );
+    assert!(TableDrill::Century.check(dt, 0));
+    assert_eq!(TableDrill::Century.question(dt), "1900s");
+}
+
+#[test]
+fn century_offset_check() {
+    for century in 15..22 {
+        let year = (century * 100).max(1584);
+        assert_eq!(
+            century_offset(century),
+            *YEARS.get(&year).unwrap(),
+            "testing {}",
+            year
+        );
+    }
+    assert_eq!(century_offset(22), 2);
+}
+
+#[test]
+fn numbering_check() {
+    for numbering in [Numbering::Devi, Numbering::Iso] {
+        for n in 0..7 {
+            let weekday = Weekday::from_u32(n).unwrap();
+            let number = numbering.number(weekday).to_string();
+            assert_eq!(
+                numbering.parse(&number),
+                Some(weekday),
+                "testing {}",
+                number
+            );
+        }
+    }
+    assert_eq!(Numbering::Devi.parse("0"), Some(Weekday::Sun));
+    assert_eq!(Numbering::Iso.parse("7"), Some(Weekday::Sun));
+    assert_eq!(Numbering::Devi.parse("7"), None);
+    assert_eq!(Numbering::Iso.parse("0"), None);
+}
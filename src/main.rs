@@ -5,15 +5,43 @@ use gui::run_gui;
 
 mod cli;
 mod gui;
+mod practice;
+mod protocol;
+mod report;
+mod settings;
+mod storage;
+#[cfg(not(target_arch = "wasm32"))]
+mod tui;
 
 fn main() {
     let args = cli::parse_cli();
-    match args {
-        false => {
-            run_gui();
+    if let Some(seed) = args.shared.seed {
+        shakuntala_devi_trainer::seed(seed);
+    }
+    match args.command {
+        Some(cli::Command::Gui) => {
+            run_gui(args.settings);
         }
-        true => {
-            cli::run_cli();
+        None if !args.cli => {
+            run_gui(args.settings);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        Some(cli::Command::Tui) => {
+            if let Err(error) = tui::run_tui(args.settings) {
+                eprintln!("{}", error);
+                std::process::exit(1);
+            }
+        }
+        command => {
+            let command = command.unwrap_or(cli::Command::Play {
+                mode: shakuntala_devi_trainer::session::Mode::default(),
+                difficulty: shakuntala_devi_trainer::Difficulty::default(),
+                rounds: 1,
+                endless: false,
+                protocol: false,
+                adaptive: false,
+            });
+            cli::run_cli(command, &args.shared);
         }
     };
 }
@@ -0,0 +1,187 @@
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use shakuntala_devi_trainer::blitz::{Bests, Blitz};
+use shakuntala_devi_trainer::history::{self, Attempt};
+use shakuntala_devi_trainer::repetition::{self, Card, Scheduler};
+use shakuntala_devi_trainer::session::{Challenge, TrainingSession};
+use shakuntala_devi_trainer::TableDrill;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::storage::{self, Place};
+
+const FILE_NAME: &str = "history.jsonl";
+const SCHEDULER_FILE_NAME: &str = "repetition.json";
+const BLITZ_FILE_NAME: &str = "blitz.json";
+
+fn lines(attempts: &[Attempt]) -> String {
+    attempts
+        .iter()
+        .map(|attempt| attempt.to_line() + "\n")
+        .collect()
+}
+
+//the history and the repetition state of the GUI are loaded once and saved with every attempt
+#[derive(Debug, Clone, Default)]
+pub struct Practice {
+    pub attempts: Vec<Attempt>,
+    scheduler: Scheduler,
+}
+
+impl Practice {
+    pub fn load() -> Practice {
+        Practice {
+            attempts: load(),
+            scheduler: load_scheduler(),
+        }
+    }
+
+    pub fn record(&mut self, session: &TrainingSession) {
+        if let Some(attempt) = save_attempt(session, &mut self.scheduler) {
+            self.attempts.push(attempt);
+        }
+    }
+
+    //a session left before its end is recorded as given up
+    pub fn give_up(&mut self, session: &mut TrainingSession) {
+        if session.outcome().is_none() {
+            session.give_up();
+            self.record(session);
+        }
+    }
+
+    pub fn table_challenge(&self, drill: TableDrill, first_year: u32, last_year: u32) -> Challenge {
+        scheduled_challenge(&self.scheduler, drill, first_year, last_year)
+    }
+}
+
+//only the sessions played to their end are attempts, the table drills are also graded
+fn save_attempt(session: &TrainingSession, scheduler: &mut Scheduler) -> Option<Attempt> {
+    let attempt = Attempt::new(session)?;
+    if let Err(error) = storage::append(
+        Place::Data,
+        FILE_NAME,
+        &lines(std::slice::from_ref(&attempt)),
+    ) {
+        eprintln!("Cannot save the history: {}", error);
+    }
+    if let Some(card) = Card::of(&session.challenge) {
+        scheduler.grade(card, repetition::quality(session), Utc::now());
+        save_scheduler(scheduler);
+    }
+    Some(attempt)
+}
+
+//the console and the terminal UI read the repetition state again for each attempt
+pub fn record(session: &TrainingSession) {
+    save_attempt(session, &mut load_scheduler());
+}
+
+pub fn give_up(session: &mut TrainingSession) {
+    if session.outcome().is_none() {
+        session.give_up();
+        record(session);
+    }
+}
+
+//a new state when there is none yet or it can't be read
+fn load_state<T: DeserializeOwned + Default>(name: &str, what: &str) -> T {
+    match storage::read(Place::Data, name) {
+        Some(text) => serde_json::from_str(&text).unwrap_or_else(|error| {
+            eprintln!("Ignoring the {}: {}", what, error);
+            T::default()
+        }),
+        None => T::default(),
+    }
+}
+
+fn save_state<T: Serialize>(name: &str, what: &str, state: &T) {
+    let text = serde_json::to_string(state).unwrap();
+    if let Err(error) = storage::write(Place::Data, name, &text) {
+        eprintln!("Cannot save the {}: {}", what, error);
+    }
+}
+
+fn load_scheduler() -> Scheduler {
+    load_state(SCHEDULER_FILE_NAME, "repetition state")
+}
+
+fn save_scheduler(scheduler: &Scheduler) {
+    save_state(SCHEDULER_FILE_NAME, "repetition state", scheduler)
+}
+
+pub fn blitz_best(duration: u64, first_year: u32, last_year: u32) -> Option<i32> {
+    load_state::<Bests>(BLITZ_FILE_NAME, "blitz records")
+        .best(duration, first_year, last_year)
+        .map(|best| best.score)
+}
+
+//true for a new personal best
+pub fn record_blitz(blitz: &Blitz) -> bool {
+    let mut bests: Bests = load_state(BLITZ_FILE_NAME, "blitz records");
+    let new_best = bests.submit(blitz, Utc::now());
+    if new_best {
+        save_state(BLITZ_FILE_NAME, "blitz records", &bests);
+    }
+    new_best
+}
+
+pub fn table_challenge(drill: TableDrill, first_year: u32, last_year: u32) -> Challenge {
+    scheduled_challenge(&load_scheduler(), drill, first_year, last_year)
+}
+
+//the card the scheduler wants to ask, a random date when the range has none
+fn scheduled_challenge(
+    scheduler: &Scheduler,
+    drill: TableDrill,
+    first_year: u32,
+    last_year: u32,
+) -> Challenge {
+    match scheduler.next(drill, first_year, last_year, Utc::now()) {
+        Some(card) => card.challenge(first_year, last_year),
+        None => Challenge::table(
+            drill,
+            shakuntala_devi_trainer::random_date(first_year, last_year),
+        ),
+    }
+}
+
+//the unreadable lines are skipped
+pub fn load() -> Vec<Attempt> {
+    let text = storage::read(Place::Data, FILE_NAME).unwrap_or_default();
+    let (attempts, errors) = history::parse(&text);
+    for error in errors {
+        eprintln!("Ignoring the history {}", error);
+    }
+    attempts
+}
+
+//to the standard output without a file
+pub fn export(file: Option<&Path>) -> Result<usize, String> {
+    let attempts = load();
+    let text = lines(&attempts);
+    match file {
+        Some(file) => std::fs::write(file, text),
+        None => io::stdout().write_all(text.as_bytes()),
+    }
+    .map_err(|error| error.to_string())?;
+    Ok(attempts.len())
+}
+
+//nothing is imported if a line can't be read, the attempts already there are skipped
+pub fn import(file: &Path) -> Result<(usize, usize), String> {
+    let text = std::fs::read_to_string(file).map_err(|error| error.to_string())?;
+    let (attempts, errors) = history::parse(&text);
+    if !errors.is_empty() {
+        return Err(errors.join("\n"));
+    }
+    let known = load();
+    let new: Vec<Attempt> = attempts
+        .into_iter()
+        .filter(|attempt| !known.contains(attempt))
+        .collect();
+    let skipped = text.lines().filter(|line| !line.trim().is_empty()).count() - new.len();
+    storage::append(Place::Data, FILE_NAME, &lines(&new))?;
+    Ok((new.len(), skipped))
+}
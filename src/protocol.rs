@@ -0,0 +1,297 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use shakuntala_devi_trainer::session::{Mode, TrainingSession};
+use shakuntala_devi_trainer::Difficulty;
+use std::io::{self, BufRead};
+
+use crate::cli::{self, Shared};
+use crate::{practice, report};
+
+//one JSON object per line on stdin, see the README for the messages
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Request {
+    New {
+        mode: Option<String>,
+        difficulty: Option<String>,
+    },
+    Guess {
+        answer: String,
+    },
+    Hint,
+    Solution,
+    Quit,
+}
+
+//one JSON object per line on stdout, an error never ends the session
+#[derive(Serialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Response {
+    Challenge {
+        mode: String,
+        question: String,
+        challenge: String,
+    },
+    Result {
+        guess: String,
+        correct: bool,
+        tries: u32,
+        tip: Option<String>,
+        //the possible mistakes of a wrong weekday in the order they are checked
+        mistakes: Vec<String>,
+        time: f64,
+    },
+    Hint {
+        tip: Option<String>,
+        hints: u32,
+    },
+    Solution {
+        answer: String,
+        steps: Vec<String>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+fn error(message: impl ToString) -> Response {
+    Response::Error {
+        message: message.to_string(),
+    }
+}
+
+fn parse<T: ValueEnum>(value: Option<String>, default: T) -> Result<T, String> {
+    match value {
+        Some(value) => T::from_str(&value, true),
+        None => Ok(default),
+    }
+}
+
+//a challenge stays open until it is found or its solution is asked
+struct Protocol<'a> {
+    mode: Mode,
+    difficulty: Difficulty,
+    shared: &'a Shared,
+    session: Option<TrainingSession>,
+    //the sessions over, to be recorded
+    finished: Vec<TrainingSession>,
+}
+
+impl Protocol<'_> {
+    //the challenge still open is given up
+    fn leave(&mut self) {
+        if let Some(mut session) = self.session.take() {
+            session.give_up();
+            self.finished.push(session);
+        }
+    }
+
+    //None when the player quits
+    fn handle(&mut self, line: &str) -> Option<Response> {
+        let request = match serde_json::from_str::<Request>(line) {
+            Ok(request) => request,
+            Err(e) => return Some(error(e)),
+        };
+        Some(match (request, &mut self.session) {
+            (Request::Quit, _) => {
+                self.leave();
+                return None;
+            }
+            (Request::New { .. }, Some(_)) => {
+                error("a challenge is still open, find it or ask its solution first")
+            }
+            (
+                Request::New {
+                    mode: new_mode,
+                    difficulty: new_difficulty,
+                },
+                None,
+            ) => {
+                let (mode, difficulty) = match (
+                    parse(new_mode, self.mode),
+                    parse(new_difficulty, self.difficulty),
+                ) {
+                    (Ok(Mode::Guided), _) => {
+                        return Some(error("the guided mode is only played in the console"))
+                    }
+                    (Ok(mode), Ok(difficulty)) => (mode, difficulty),
+                    (Err(e), _) | (_, Err(e)) => return Some(error(e)),
+                };
+                match cli::try_new_challenge(mode, difficulty, self.shared) {
+                    Ok(challenge) => {
+                        let new = self.session.insert(TrainingSession::new(challenge));
+                        Response::Challenge {
+                            mode: report::value_name(mode),
+                            question: new.challenge.question.clone(),
+                            challenge: new.challenge.challenge.clone(),
+                        }
+                    }
+                    Err(e) => error(e),
+                }
+            }
+            (_, None) => error("no challenge, send a new one"),
+            (Request::Guess { answer }, Some(current)) => {
+                let guess = match current.challenge.parse_with(&answer, self.shared.numbering) {
+                    Some(guess) => guess,
+                    None => return Some(error(format!("cannot read the answer {:?}", answer))),
+                };
+                let feedback = match current.guess(guess.clone()) {
+                    Some(feedback) => feedback,
+                    None => return Some(error(format!("{} was already tried", guess))),
+                };
+                let response = Response::Result {
+                    guess: guess.to_string(),
+                    correct: feedback.correct,
+                    tries: feedback.tries,
+                    tip: feedback.tip,
+                    mistakes: feedback
+                        .mistakes
+                        .iter()
+                        .map(|mistake| mistake.to_string())
+                        .collect(),
+                    time: current.elapsed().as_secs_f64(),
+                };
+                if feedback.correct {
+                    self.finished.extend(self.session.take());
+                }
+                response
+            }
+            (Request::Hint, Some(current)) => {
+                let tip = current.hint();
+                Response::Hint {
+                    tip,
+                    hints: current.hints(),
+                }
+            }
+            (Request::Solution, Some(current)) => {
+                current.reveal();
+                let response = Response::Solution {
+                    answer: current.challenge.answer.clone(),
+                    steps: current.challenge.tips.0.iter().cloned().collect(),
+                };
+                self.finished.extend(self.session.take());
+                response
+            }
+        })
+    }
+}
+
+pub fn run(mode: Mode, difficulty: Difficulty, shared: &Shared) {
+    let mut protocol = Protocol {
+        mode,
+        difficulty,
+        shared,
+        session: None,
+        finished: vec![],
+    };
+    for line in io::stdin().lock().lines() {
+        let line = line.expect("Failed to read line");
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = protocol.handle(&line);
+        for session in protocol.finished.drain(..) {
+            practice::record(&session);
+        }
+        match response {
+            Some(response) => println!("{}", serde_json::to_string(&response).unwrap()),
+            None => return,
+        }
+    }
+    //the end of the input
+    protocol.leave();
+    for session in protocol.finished.drain(..) {
+        practice::record(&session);
+    }
+}
+
+#[cfg(test)]
+fn test_shared() -> Shared {
+    Shared {
+        from: 1900,
+        to: 2000,
+        method: shakuntala_devi_trainer::Method::ShakuntalaDevi,
+        seed: None,
+        format: cli::Format::Text,
+        numbering: shakuntala_devi_trainer::Numbering::Devi,
+        timer: true,
+    }
+}
+
+#[cfg(test)]
+fn round_trip(protocol: &mut Protocol, line: &str) -> serde_json::Value {
+    serde_json::to_value(protocol.handle(line).unwrap()).unwrap()
+}
+
+#[test]
+fn protocol_check() {
+    use shakuntala_devi_trainer::session::{Expected, Outcome};
+    let shared = test_shared();
+    let mut protocol = Protocol {
+        mode: Mode::Day,
+        difficulty: Difficulty::default(),
+        shared: &shared,
+        session: None,
+        finished: vec![],
+    };
+    let response = round_trip(&mut protocol, r#"{"type":"guess","answer":"1"}"#);
+    assert_eq!(response["type"], "error");
+    let response = round_trip(&mut protocol, r#"{"type":"new"}"#);
+    assert_eq!(response["type"], "challenge");
+    assert_eq!(response["mode"], "day");
+    let response = round_trip(&mut protocol, r#"{"type":"new","mode":"puzzle"}"#);
+    assert_eq!(response["type"], "error");
+    let weekday = match protocol.session.as_ref().unwrap().challenge.expected {
+        Expected::Weekday(weekday) => weekday,
+        _ => unreachable!(),
+    };
+    let wrong = weekday.succ().num_days_from_sunday();
+    let response = round_trip(
+        &mut protocol,
+        &format!(r#"{{"type":"guess","answer":"{}"}}"#, wrong),
+    );
+    assert_eq!(response["type"], "result");
+    assert_eq!(response["correct"], false);
+    assert_eq!(response["tries"], 1);
+    let response = round_trip(
+        &mut protocol,
+        &format!(r#"{{"type":"guess","answer":"{}"}}"#, wrong),
+    );
+    assert_eq!(response["type"], "error");
+    let response = round_trip(&mut protocol, r#"{"type":"guess","answer":"monday"}"#);
+    assert_eq!(response["type"], "error");
+    let response = round_trip(&mut protocol, r#"{"type":"hint"}"#);
+    assert_eq!(response["type"], "hint");
+    assert_eq!(response["hints"], 1);
+    let response = round_trip(
+        &mut protocol,
+        &format!(
+            r#"{{"type":"guess","answer":"{}"}}"#,
+            weekday.num_days_from_sunday()
+        ),
+    );
+    assert_eq!(response["correct"], true);
+    assert!(protocol.session.is_none());
+    assert_eq!(protocol.finished.len(), 1);
+    assert_eq!(protocol.finished[0].outcome(), Some(Outcome::Found));
+
+    round_trip(&mut protocol, r#"{"type":"new","mode":"weekday-drill"}"#);
+    let response = round_trip(&mut protocol, r#"{"type":"solution"}"#);
+    assert_eq!(response["type"], "solution");
+    assert_eq!(
+        response["answer"].as_str(),
+        Some(protocol.finished[1].challenge.answer.as_str())
+    );
+    assert_eq!(protocol.finished[1].outcome(), Some(Outcome::GaveUp));
+
+    let response = round_trip(&mut protocol, r#"{"type":"new","mode":"guided"}"#);
+    assert_eq!(response["type"], "error");
+    let response = round_trip(&mut protocol, r#"{"type":"new","mode":"nope"}"#);
+    assert_eq!(response["type"], "error");
+    let response = round_trip(&mut protocol, "not json");
+    assert_eq!(response["type"], "error");
+    round_trip(&mut protocol, r#"{"type":"new"}"#);
+    assert!(protocol.handle(r#"{"type":"quit"}"#).is_none());
+    assert_eq!(protocol.finished.len(), 3);
+    assert_eq!(protocol.finished[2].outcome(), Some(Outcome::GaveUp));
+}
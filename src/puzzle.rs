@@ -0,0 +1,307 @@
+use chrono::prelude::*;
+use num_traits::cast::FromPrimitive;
+use std::collections::VecDeque;
+use std::fmt;
+
+use crate::{random_date, random_range, shakuntala_devi, weekday_name, Method, Tips};
+
+//how many years the "in which years" puzzles span
+pub const PUZZLE_YEAR_SPAN: i32 = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Puzzle {
+    YearsWithWeekday {
+        day: u32,
+        month: u32,
+        weekday: Weekday,
+        from_year: i32,
+        to_year: i32,
+    },
+    MonthsStartingOn {
+        year: i32,
+        weekday: Weekday,
+    },
+    NextFriday13 {
+        after: NaiveDate,
+    },
+}
+
+pub fn years_with_weekday(
+    day: u32,
+    month: u32,
+    weekday: Weekday,
+    from_year: i32,
+    to_year: i32,
+) -> Vec<i32> {
+    (from_year..=to_year)
+        .filter(|&year| {
+            NaiveDate::from_ymd_opt(year, month, day)
+                .is_some_and(|dt| shakuntala_devi(dt).0 == weekday)
+        })
+        .collect()
+}
+
+pub fn months_starting_on(year: i32, weekday: Weekday) -> Vec<u32> {
+    (1..=12)
+        .filter(|&month| {
+            shakuntala_devi(NaiveDate::from_ymd_opt(year, month, 1).unwrap()).0 == weekday
+        })
+        .collect()
+}
+
+pub fn next_friday_13(after: NaiveDate) -> NaiveDate {
+    let (mut year, mut month) = if after.day() < 13 {
+        (after.year(), after.month())
+    } else if after.month() == 12 {
+        (after.year() + 1, 1)
+    } else {
+        (after.year(), after.month() + 1)
+    };
+    loop {
+        let dt = NaiveDate::from_ymd_opt(year, month, 13).unwrap();
+        if shakuntala_devi(dt).0 == Weekday::Fri {
+            return dt;
+        }
+        (year, month) = if month == 12 {
+            (year + 1, 1)
+        } else {
+            (year, month + 1)
+        };
+    }
+}
+
+impl Puzzle {
+    pub fn solve(&self) -> Vec<NaiveDate> {
+        match *self {
+            Puzzle::YearsWithWeekday {
+                day,
+                month,
+                weekday,
+                from_year,
+                to_year,
+            } => years_with_weekday(day, month, weekday, from_year, to_year)
+                .into_iter()
+                .map(|year| NaiveDate::from_ymd_opt(year, month, day).unwrap())
+                .collect(),
+            Puzzle::MonthsStartingOn { year, weekday } => months_starting_on(year, weekday)
+                .into_iter()
+                .map(|month| NaiveDate::from_ymd_opt(year, month, 1).unwrap())
+                .collect(),
+            Puzzle::NextFriday13 { after } => vec![next_friday_13(after)],
+        }
+    }
+
+    //years for the first kind, month numbers or names for the second, a date or a year-month for the last
+    pub fn parse_answer(&self, input: &str) -> Option<Vec<NaiveDate>> {
+        let items = input
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|item| !item.is_empty());
+        match *self {
+            Puzzle::YearsWithWeekday { day, month, .. } => items
+                .map(|item| {
+                    item.parse::<i32>()
+                        .ok()
+                        .and_then(|year| NaiveDate::from_ymd_opt(year, month, day))
+                })
+                .collect(),
+            Puzzle::MonthsStartingOn { year, .. } => items
+                .map(|item| {
+                    let month = match item.parse::<u32>() {
+                        Ok(month) => month,
+                        Err(_) => item.parse::<Month>().ok()?.number_from_month(),
+                    };
+                    NaiveDate::from_ymd_opt(year, month, 1)
+                })
+                .collect(),
+            Puzzle::NextFriday13 { .. } => items
+                .map(|item| {
+                    let item = if item.len() <= 7 {
+                        format!("{}-13", item)
+                    } else {
+                        item.to_string()
+                    };
+                    NaiveDate::parse_from_str(&item, "%Y-%m-%d").ok()
+                })
+                .collect(),
+        }
+    }
+
+    pub fn answer_format(&self) -> &'static str {
+        match self {
+            Puzzle::YearsWithWeekday { .. } => "years separated by commas",
+            Puzzle::MonthsStartingOn { .. } => "month numbers or names separated by commas",
+            Puzzle::NextFriday13 { .. } => "a date like 2026-11-13",
+        }
+    }
+
+    pub fn check(&self, answer: &[NaiveDate]) -> bool {
+        let mut answer = answer.to_vec();
+        answer.sort();
+        answer.dedup();
+        answer == self.solve()
+    }
+
+    pub fn tips(&self) -> Tips {
+        let mut v: Tips = Tips(VecDeque::new());
+        let solution = self.solve();
+        match self {
+            Puzzle::YearsWithWeekday { .. } => {
+                v.0.push_back(format!("{} years expected", solution.len()));
+                v.0.push_back(
+                    "a date moves one weekday forward each year, two after a 29 February"
+                        .to_string(),
+                );
+            }
+            Puzzle::MonthsStartingOn { .. } => {
+                v.0.push_back(format!("{} months expected", solution.len()));
+                v.0.push_back(
+                    "the next month starts (31 days) 3, (30 days) 2, (29 days) 1 or (28 days) 0 weekdays later"
+                        .to_string(),
+                );
+            }
+            Puzzle::NextFriday13 { .. } => {
+                v.0.push_back("a month has a Friday 13th when it starts on a Sunday".to_string());
+            }
+        }
+        for dt in solution {
+            v.0.push_back(format!("{} is a {}", dt, weekday_name(dt.weekday())));
+        }
+        v
+    }
+}
+
+impl fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Puzzle::YearsWithWeekday {
+                day,
+                month,
+                weekday,
+                from_year,
+                to_year,
+            } => write!(
+                f,
+                "In which years between {} and {} was {} {} a {} ?",
+                from_year,
+                to_year,
+                day,
+                Month::from_u32(month).unwrap().name(),
+                weekday_name(weekday)
+            ),
+            Puzzle::MonthsStartingOn { year, weekday } => write!(
+                f,
+                "Which months of {} start on a {} ?",
+                year,
+                weekday_name(weekday)
+            ),
+            Puzzle::NextFriday13 { after } => {
+                write!(f, "When is the next Friday 13th after {} ?", after)
+            }
+        }
+    }
+}
+
+//the weekday is taken from a date of the puzzle so there is always at least one answer
+pub fn random_puzzle(from_year: u32, to_year: u32) -> Puzzle {
+    let dt = random_date(from_year, to_year);
+    match random_range(0..3) {
+        //the span stays in the range asked and in the years the method supports
+        0 => {
+            let supported = Method::ShakuntalaDevi.supported_years();
+            let first_year = from_year.max(*supported.start()) as i32;
+            let last_year = to_year.min(*supported.end()) as i32;
+            let from_year = (dt.year() - random_range(0..=PUZZLE_YEAR_SPAN))
+                .min(last_year - PUZZLE_YEAR_SPAN)
+                .max(first_year);
+            Puzzle::YearsWithWeekday {
+                day: dt.day(),
+                month: dt.month(),
+                weekday: shakuntala_devi(dt).0,
+                from_year,
+                to_year: (from_year + PUZZLE_YEAR_SPAN).min(last_year),
+            }
+        }
+        1 => Puzzle::MonthsStartingOn {
+            year: dt.year(),
+            weekday: shakuntala_devi(dt.with_day(1).unwrap()).0,
+        },
+        _ => Puzzle::NextFriday13 { after: dt },
+    }
+}
+
+#[test]
+fn years_with_weekday_unit_check() {
+    assert_eq!(
+        years_with_weekday(25, 12, Weekday::Sun, 1990, 2010),
+        vec![1994, 2005]
+    );
+}
+
+#[test]
+fn months_starting_on_unit_check() {
+    assert_eq!(months_starting_on(2031, Weekday::Fri), vec![8]);
+    assert_eq!(months_starting_on(2026, Weekday::Sun), vec![2, 3, 11]);
+}
+
+#[test]
+fn next_friday_13_check() {
+    let calendar = NaiveDate::from_ymd_opt(1900, 1, 1).unwrap().iter_days();
+    for dt in calendar {
+        let friday_13 = next_friday_13(dt);
+        assert!(friday_13 > dt, "testing {}", dt);
+        assert_eq!(friday_13.weekday(), Weekday::Fri, "testing {}", dt);
+        assert_eq!(friday_13.day(), 13, "testing {}", dt);
+        assert!(
+            dt.iter_days()
+                .skip(1)
+                .take_while(|&d| d < friday_13)
+                .all(|d| d.day() != 13 || d.weekday() != Weekday::Fri),
+            "testing {}",
+            dt
+        );
+        if dt.year() == 2100 {
+            break;
+        };
+    }
+}
+
+#[test]
+fn puzzle_answer_check() {
+    let puzzle = Puzzle::MonthsStartingOn {
+        year: 2026,
+        weekday: Weekday::Sun,
+    };
+    assert!(puzzle.check(&puzzle.parse_answer("November, 2 march").unwrap()));
+    assert!(!puzzle.check(&puzzle.parse_answer("2 3").unwrap()));
+    assert!(puzzle.parse_answer("2 foo").is_none());
+
+    let puzzle = Puzzle::NextFriday13 {
+        after: NaiveDate::from_ymd_opt(2026, 10, 19).unwrap(),
+    };
+    assert!(puzzle.check(&puzzle.parse_answer("2026-11").unwrap()));
+    assert!(puzzle.check(&puzzle.parse_answer("2026-11-13").unwrap()));
+}
+
+#[test]
+fn random_puzzle_check() {
+    for _ in 0..100 {
+        let puzzle = random_puzzle(crate::DEFAULT_FIRST_YEAR, crate::DEFAULT_LAST_YEAR);
+        let solution = puzzle.solve();
+        assert!(!solution.is_empty(), "testing {}", puzzle);
+        assert!(puzzle.check(&solution), "testing {}", puzzle);
+    }
+}
+
+#[test]
+fn random_puzzle_narrow_range_check() {
+    for _ in 0..200 {
+        let puzzle = random_puzzle(2195, 2203);
+        if let Puzzle::YearsWithWeekday {
+            from_year, to_year, ..
+        } = puzzle
+        {
+            assert!(2195 <= from_year && to_year <= 2203, "testing {}", puzzle);
+        }
+        assert!(!puzzle.solve().is_empty(), "testing {}", puzzle);
+    }
+}
@@ -0,0 +1,285 @@
+use chrono::prelude::*;
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+
+use crate::session::{Challenge, Mode, Outcome, TrainingSession};
+use crate::{shakuntala_devi_nearest_leap_year, TableDrill, YEARS};
+
+//one flashcard: a month table entry, the entry of a leap year of the year table or a century offset
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Card {
+    Month(u32),
+    Year(i32),
+    Century(i32),
+}
+
+//the years without a direct entry are asked with the entry of their nearest leap year
+fn year_card(year: i32) -> Card {
+    let versatile_answer = shakuntala_devi_nearest_leap_year(year, &mut None);
+    Card::Year(if versatile_answer > 12 {
+        versatile_answer
+    } else {
+        year
+    })
+}
+
+impl Card {
+    //the other years are asked with the entry of their nearest leap year
+    pub fn of(challenge: &Challenge) -> Option<Card> {
+        let dt = challenge.date?;
+        match challenge.mode {
+            Mode::MonthTable => Some(Card::Month(dt.month())),
+            Mode::YearTable => Some(year_card(dt.year())),
+            Mode::CenturyTable => Some(Card::Century(dt.year() / 100)),
+            _ => None,
+        }
+    }
+
+    //the year table drill also asks the century offsets its entries are built on
+    pub fn cards(drill: TableDrill, first_year: u32, last_year: u32) -> Vec<Card> {
+        let years = first_year as i32..=last_year as i32;
+        let months = (1..=12).map(Card::Month).collect();
+        //a leap year before the range is still asked through the years after it
+        let mut leap_years: Vec<Card> = YEARS
+            .keys()
+            .filter(|&&year| {
+                NaiveDate::from_ymd_opt(year, 2, 29).is_some()
+                    && (year..year + 8).any(|covered| {
+                        years.contains(&covered) && year_card(covered) == Card::Year(year)
+                    })
+            })
+            .map(|&year| Card::Year(year))
+            .collect();
+        leap_years.sort();
+        let centuries: Vec<Card> = (years.start() / 100..=years.end() / 100)
+            .map(Card::Century)
+            .collect();
+        match drill {
+            TableDrill::Month => months,
+            TableDrill::Year => [leap_years, centuries].concat(),
+            TableDrill::Century => centuries,
+        }
+    }
+
+    pub fn drill(self) -> TableDrill {
+        match self {
+            Card::Month(_) => TableDrill::Month,
+            Card::Year(_) => TableDrill::Year,
+            Card::Century(_) => TableDrill::Century,
+        }
+    }
+
+    //a date of the year range the card can be asked with
+    pub fn date(self, first_year: u32, last_year: u32) -> NaiveDate {
+        let (year, month) = match self {
+            Card::Month(month) => (
+                crate::random_range(first_year as i32..=last_year as i32),
+                month,
+            ),
+            //the leap year or one of the years asked with its entry
+            Card::Year(year) => {
+                let covered: Vec<i32> = (year..year + 8)
+                    .filter(|&covered| {
+                        (first_year as i32..=last_year as i32).contains(&covered)
+                            && year_card(covered) == self
+                    })
+                    .collect();
+                match covered.len() {
+                    0 => (year, 1),
+                    n => (covered[crate::random_range(0..n)], 1),
+                }
+            }
+            Card::Century(century) => (
+                (century * 100).clamp(first_year as i32, last_year as i32),
+                1,
+            ),
+        };
+        NaiveDate::from_ymd_opt(year, month, 1).unwrap()
+    }
+
+    pub fn challenge(self, first_year: u32, last_year: u32) -> Challenge {
+        Challenge::table(self.drill(), self.date(first_year, last_year))
+    }
+}
+
+//SM-2 grades from 0 to 5, below 3 the card is forgotten
+pub fn quality(session: &TrainingSession) -> u8 {
+    match (
+        session.found_at_first_guess(),
+        session.hints(),
+        session.outcome(),
+    ) {
+        (true, 0, _) if session.elapsed().as_secs() < 5 => 5,
+        (true, 0, _) => 4,
+        (true, _, _) => 3,
+        (false, _, Some(Outcome::Found)) => 1,
+        _ => 0,
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Review {
+    pub card: Card,
+    pub repetitions: u32,
+    pub easiness: f64,
+    //in days
+    pub interval: i64,
+    pub due: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Scheduler {
+    reviews: Vec<Review>,
+}
+
+impl Scheduler {
+    pub fn review(&self, card: Card) -> Option<&Review> {
+        self.reviews.iter().find(|review| review.card == card)
+    }
+
+    //https://super-memory.com/english/ol/sm2.htm
+    //a forgotten card comes back after ten minutes instead of the next day
+    pub fn grade(&mut self, card: Card, quality: u8, now: DateTime<Utc>) {
+        let index = match self.reviews.iter().position(|review| review.card == card) {
+            Some(index) => index,
+            None => {
+                self.reviews.push(Review {
+                    card,
+                    repetitions: 0,
+                    easiness: 2.5,
+                    interval: 0,
+                    due: now,
+                });
+                self.reviews.len() - 1
+            }
+        };
+        let review = &mut self.reviews[index];
+        let q = f64::from(5 - quality.min(5));
+        review.easiness = (review.easiness + 0.1 - q * (0.08 + q * 0.02)).max(1.3);
+        if quality >= 3 {
+            review.interval = match review.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => (review.interval as f64 * review.easiness).round() as i64,
+            };
+            review.repetitions += 1;
+            review.due = now + Duration::days(review.interval);
+        } else {
+            review.repetitions = 0;
+            review.interval = 0;
+            review.due = now + Duration::minutes(10);
+        }
+    }
+
+    //the most overdue card, then a new one, then the next one due
+    pub fn next(
+        &self,
+        drill: TableDrill,
+        first_year: u32,
+        last_year: u32,
+        now: DateTime<Utc>,
+    ) -> Option<Card> {
+        let cards = Card::cards(drill, first_year, last_year);
+        let reviews: Vec<&Review> = self
+            .reviews
+            .iter()
+            .filter(|review| cards.contains(&review.card))
+            .collect();
+        let earliest = reviews.iter().min_by_key(|review| review.due);
+        if let Some(review) = earliest.filter(|review| review.due <= now) {
+            return Some(review.card);
+        }
+        let new: Vec<Card> = cards
+            .into_iter()
+            .filter(|&card| self.review(card).is_none())
+            .collect();
+        if !new.is_empty() {
+            return Some(new[crate::random_range(0..new.len())]);
+        }
+        earliest.map(|review| review.card)
+    }
+}
+
+#[test]
+fn card_check() {
+    let dt = NaiveDate::from_ymd_opt(1983, 2, 1).unwrap();
+    assert_eq!(
+        Card::of(&Challenge::table(TableDrill::Month, dt)),
+        Some(Card::Month(2))
+    );
+    assert_eq!(
+        Card::of(&Challenge::table(TableDrill::Year, dt)),
+        Some(Card::Year(1980))
+    );
+    assert_eq!(
+        Card::of(&Challenge::table(TableDrill::Century, dt)),
+        Some(Card::Century(19))
+    );
+    let cards = Card::cards(TableDrill::Year, 1896, 1904);
+    assert_eq!(
+        cards,
+        vec![
+            Card::Year(1896),
+            Card::Year(1904),
+            Card::Century(18),
+            Card::Century(19)
+        ]
+    );
+    for card in cards {
+        let challenge = card.challenge(1896, 1904);
+        assert_eq!(Card::of(&challenge), Some(card));
+    }
+    //the common years are asked with the card of their leap year
+    assert_eq!(
+        Card::cards(TableDrill::Year, 1901, 1903),
+        vec![Card::Year(1896), Card::Century(19)]
+    );
+    let mut asked = vec![];
+    for _ in 0..50 {
+        let challenge = Card::Year(1980).challenge(1900, 2000);
+        assert_eq!(Card::of(&challenge), Some(Card::Year(1980)));
+        asked.push(challenge.date.unwrap().year());
+    }
+    asked.sort();
+    asked.dedup();
+    assert_eq!(asked, vec![1980, 1981, 1982, 1983]);
+}
+
+#[test]
+fn scheduler_check() {
+    let now: DateTime<Utc> = "2026-10-19T10:00:00Z".parse().unwrap();
+    let mut scheduler = Scheduler::default();
+    let card = Card::Month(2);
+    scheduler.grade(card, 5, now);
+    scheduler.grade(card, 5, now);
+    scheduler.grade(card, 4, now);
+    let review = scheduler.review(card).unwrap();
+    assert_eq!((review.repetitions, review.interval), (3, 16));
+    assert!((review.easiness - 2.7).abs() < 1e-9);
+    scheduler.grade(card, 1, now);
+    let review = scheduler.review(card).unwrap();
+    assert_eq!((review.repetitions, review.interval), (0, 0));
+    assert_eq!(review.due, now + Duration::minutes(10));
+
+    //every new month is asked before a card comes back
+    let mut asked = vec![];
+    for _ in 0..11 {
+        let next = scheduler.next(TableDrill::Month, 1900, 2000, now).unwrap();
+        scheduler.grade(next, 4, now);
+        asked.push(next);
+    }
+    asked.sort();
+    asked.dedup();
+    assert_eq!(asked.len(), 11);
+    assert!(!asked.contains(&card));
+    assert_eq!(
+        scheduler.next(TableDrill::Month, 1900, 2000, now),
+        Some(card)
+    );
+    let later = now + Duration::minutes(11);
+    assert_eq!(
+        scheduler.next(TableDrill::Month, 1900, 2000, later),
+        Some(card)
+    );
+}
@@ -0,0 +1,130 @@
+use chrono::prelude::*;
+use clap::ValueEnum;
+use serde::Serialize;
+use shakuntala_devi_trainer::stats::Breakdown;
+
+//JSON documents printed with --format json, see the README for the schema
+//bump the version when a field changes meaning or disappears, new fields keep it
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Report {
+    Solve {
+        date: NaiveDate,
+        weekday: Day,
+        method: String,
+        steps: Vec<String>,
+    },
+    Play {
+        mode: String,
+        rounds: Vec<RoundReport>,
+        first_guess: usize,
+        tries: u32,
+        accuracy: f64,
+        average_time: f64,
+    },
+    Table {
+        month_table: Vec<MonthEntry>,
+        year_table: Vec<YearEntry>,
+    },
+    Verify {
+        method: String,
+        from: u32,
+        to: u32,
+        checked: u32,
+        errors: u32,
+        mismatches: Vec<Mismatch>,
+    },
+    Stats {
+        attempts: usize,
+        breakdowns: Vec<Breakdown>,
+    },
+    Calendar {
+        from: u32,
+        to: u32,
+        leap_years: usize,
+        friday_13: u32,
+        weekdays: Vec<WeekdayCount>,
+    },
+}
+
+//name and Shakuntala Devi number, Sunday being 0
+#[derive(Serialize, Debug)]
+pub struct Day {
+    pub name: &'static str,
+    pub number: u32,
+}
+
+impl From<Weekday> for Day {
+    fn from(weekday: Weekday) -> Day {
+        Day {
+            name: shakuntala_devi_trainer::weekday_name(weekday),
+            number: weekday.num_days_from_sunday(),
+        }
+    }
+}
+
+//the weekday only comes with the challenges answered by a weekday
+#[derive(Serialize, Debug)]
+pub struct RoundReport {
+    pub round: usize,
+    pub method: String,
+    pub challenge: String,
+    pub answer: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weekday: Option<Day>,
+    pub steps: Vec<String>,
+    pub tries: u32,
+    pub hints: u32,
+    pub time: f64,
+    pub result: &'static str,
+}
+
+#[derive(Serialize, Debug)]
+pub struct MonthEntry {
+    pub month: &'static str,
+    pub entry: i32,
+}
+
+#[derive(Serialize, Debug)]
+pub struct YearEntry {
+    pub year: i32,
+    pub entry: i32,
+}
+
+#[derive(Serialize, Debug)]
+pub struct WeekdayCount {
+    pub weekday: Day,
+    pub days: u32,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Mismatch {
+    pub date: NaiveDate,
+    pub found: Day,
+    pub expected: Day,
+}
+
+//the command line spelling, like shakuntala-devi or days-between
+pub fn value_name(value: impl ValueEnum) -> String {
+    value.to_possible_value().unwrap().get_name().to_string()
+}
+
+//one document per line so the output of a session can be read line by line
+pub fn print(report: Report) {
+    #[derive(Serialize)]
+    struct Versioned {
+        schema: u32,
+        #[serde(flatten)]
+        report: Report,
+    }
+    println!(
+        "{}",
+        serde_json::to_string(&Versioned {
+            schema: SCHEMA_VERSION,
+            report,
+        })
+        .unwrap()
+    );
+}
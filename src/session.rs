@@ -0,0 +1,562 @@
+use chrono::prelude::*;
+use std::fmt;
+use std::time::Duration;
+use web_time::Instant;
+
+use crate::diagnosis::{self, Mistake};
+use crate::{
+    days_between, puzzle::Puzzle, weekday_name, DrillStart, Method, Numbering, TableDrill, Tips,
+    WeekdayDrill,
+};
+
+//the trainings of the console version, the GUI has a screen for each of them
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    clap::ValueEnum,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Mode {
+    #[default]
+    Day,
+    DaysBetween,
+    WeekdayDrill,
+    Puzzle,
+    MonthTable,
+    YearTable,
+    CenturyTable,
+    Guided,
+    //played with the blitz command, not a mode of play
+    #[value(skip)]
+    Blitz,
+}
+
+//what the player has to find, a guess is checked against it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expected {
+    Weekday(Weekday),
+    Entry(i32),
+    Days(i64),
+    Puzzle(Puzzle),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Guess {
+    Weekday(Weekday),
+    Entry(i32),
+    Days(i64),
+    Dates(Vec<NaiveDate>),
+}
+
+impl fmt::Display for Guess {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Guess::Weekday(weekday) => write!(f, "{}", weekday),
+            Guess::Entry(entry) => write!(f, "{}", entry),
+            Guess::Days(days) => write!(f, "{} days", days),
+            Guess::Dates(dates) => write!(
+                f,
+                "{}",
+                dates
+                    .iter()
+                    .map(|dt| dt.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Challenge {
+    pub mode: Mode,
+    pub method: Method,
+    //the date the player works on, if any
+    pub date: Option<NaiveDate>,
+    pub question: String,
+    pub challenge: String,
+    pub answer: String,
+    pub expected: Expected,
+    pub tips: Tips,
+}
+
+impl Challenge {
+    pub fn day(method: Method, dt: NaiveDate) -> Challenge {
+        let (weekday, tips) = method.solve(dt);
+        Challenge {
+            mode: Mode::Day,
+            method,
+            date: Some(dt),
+            question: format!("{} found the day of {:#?} can you to ?", method.name(), dt),
+            challenge: dt.to_string(),
+            answer: weekday_name(weekday).to_string(),
+            expected: Expected::Weekday(weekday),
+            tips,
+        }
+    }
+
+    //a day challenge of the blitz, kept apart so its rushed guesses don't count as day games
+    pub fn blitz(method: Method, dt: NaiveDate) -> Challenge {
+        Challenge {
+            mode: Mode::Blitz,
+            ..Challenge::day(method, dt)
+        }
+    }
+
+    //the day challenge answered one step of Shakuntala Devi's method at a time
+    pub fn guided(dt: NaiveDate) -> Challenge {
+        Challenge {
+            mode: Mode::Guided,
+            question: format!("Find the day of {:#?} step by step", dt),
+            ..Challenge::day(Method::ShakuntalaDevi, dt)
+        }
+    }
+
+    pub fn days_between(first_date: NaiveDate, second_date: NaiveDate) -> Challenge {
+        let (days, tips) = days_between(first_date, second_date);
+        Challenge {
+            mode: Mode::DaysBetween,
+            method: Method::ShakuntalaDevi,
+            date: None,
+            question: format!(
+                "How many days between {:#?} and {:#?} ?",
+                first_date, second_date
+            ),
+            challenge: format!("{} to {}", first_date, second_date),
+            answer: format!("{} days", days),
+            expected: Expected::Days(days),
+            tips,
+        }
+    }
+
+    pub fn weekday_drill(drill: &WeekdayDrill) -> Challenge {
+        Challenge {
+            mode: Mode::WeekdayDrill,
+            method: Method::ShakuntalaDevi,
+            date: match drill.start {
+                DrillStart::Weekday(_) => None,
+                DrillStart::Date(dt) => Some(dt),
+            },
+            question: format!("Which day is {} ?", drill),
+            challenge: drill.to_string(),
+            answer: weekday_name(drill.answer).to_string(),
+            expected: Expected::Weekday(drill.answer),
+            tips: drill.tips.clone(),
+        }
+    }
+
+    pub fn puzzle(puzzle: Puzzle) -> Challenge {
+        Challenge {
+            mode: Mode::Puzzle,
+            method: Method::ShakuntalaDevi,
+            date: None,
+            question: format!("{} ({})", puzzle, puzzle.answer_format()),
+            challenge: puzzle.to_string(),
+            answer: puzzle
+                .solve()
+                .iter()
+                .map(|dt| dt.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            expected: Expected::Puzzle(puzzle),
+            tips: puzzle.tips(),
+        }
+    }
+
+    pub fn table(drill: TableDrill, dt: NaiveDate) -> Challenge {
+        let question = drill.question(dt);
+        Challenge {
+            mode: match drill {
+                TableDrill::Month => Mode::MonthTable,
+                TableDrill::Year => Mode::YearTable,
+                TableDrill::Century => Mode::CenturyTable,
+            },
+            method: Method::ShakuntalaDevi,
+            date: Some(dt),
+            question: match drill {
+                TableDrill::Month => format!("Which is the month table entry of {} ?", question),
+                TableDrill::Year => format!("Which is the year table entry of {} ?", question),
+                TableDrill::Century => format!("Which is the century offset of the {} ?", question),
+            },
+            challenge: question,
+            answer: drill.answer(dt).to_string(),
+            expected: Expected::Entry(drill.answer(dt)),
+            tips: drill.tips(dt),
+        }
+    }
+
+    //weekdays are read with Shakuntala Devi's numbering
+    pub fn parse(&self, input: &str) -> Option<Guess> {
+        self.parse_with(input, Numbering::Devi)
+    }
+
+    //None when the input can't be read as an answer of this challenge
+    pub fn parse_with(&self, input: &str, numbering: Numbering) -> Option<Guess> {
+        match self.expected {
+            Expected::Weekday(_) => numbering.parse(input).map(Guess::Weekday),
+            Expected::Entry(_) => input
+                .parse::<i32>()
+                .ok()
+                .filter(|entry| (0..7).contains(entry))
+                .map(Guess::Entry),
+            Expected::Days(_) => input.parse::<i64>().ok().map(Guess::Days),
+            Expected::Puzzle(puzzle) => puzzle
+                .parse_answer(input)
+                .filter(|dates| !dates.is_empty())
+                .map(Guess::Dates),
+        }
+    }
+
+    //only the day challenges of Shakuntala Devi's method have steps to check
+    pub fn diagnose(&self, guess: &Guess) -> Vec<Mistake> {
+        match (self.mode, self.method, self.date, guess) {
+            (
+                Mode::Day | Mode::Guided | Mode::Blitz,
+                Method::ShakuntalaDevi,
+                Some(dt),
+                Guess::Weekday(weekday),
+            ) => diagnosis::diagnose(dt, *weekday),
+            _ => vec![],
+        }
+    }
+
+    pub fn check(&self, guess: &Guess) -> bool {
+        match (self.expected, guess) {
+            (Expected::Weekday(expected), Guess::Weekday(guess)) => expected == *guess,
+            (Expected::Entry(expected), Guess::Entry(guess)) => expected == *guess,
+            (Expected::Days(expected), Guess::Days(guess)) => expected == *guess,
+            (Expected::Puzzle(puzzle), Guess::Dates(guess)) => puzzle.check(guess),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Found,
+    Skipped,
+    GaveUp,
+    TimedOut,
+}
+
+//answer to a guess, the next tip and the likely mistakes come with a wrong guess
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Feedback {
+    pub correct: bool,
+    pub tries: u32,
+    pub tip: Option<String>,
+    pub mistakes: Vec<Mistake>,
+}
+
+//one challenge from its start to its outcome, tips are given in order to hints and wrong guesses
+#[derive(Debug, Clone)]
+pub struct TrainingSession {
+    pub challenge: Challenge,
+    guesses: Vec<Guess>,
+    next_tip: usize,
+    hints: u32,
+    start: Instant,
+    end: Option<(Outcome, Duration)>,
+}
+
+impl TrainingSession {
+    pub fn new(challenge: Challenge) -> TrainingSession {
+        TrainingSession {
+            challenge,
+            guesses: vec![],
+            next_tip: 0,
+            hints: 0,
+            start: Instant::now(),
+            end: None,
+        }
+    }
+
+    //the table drills have a single tip, it is repeated on every wrong guess
+    fn next_tip(&mut self) -> Option<String> {
+        self.next_tip += 1;
+        let tips = &self.challenge.tips.0;
+        match self.challenge.mode {
+            Mode::MonthTable | Mode::YearTable | Mode::CenturyTable => {
+                tips.get(self.next_tip - 1).or(tips.back()).cloned()
+            }
+            _ => tips.get(self.next_tip - 1).cloned(),
+        }
+    }
+
+    fn finish(&mut self, outcome: Outcome) {
+        self.end = Some((outcome, self.start.elapsed()));
+    }
+
+    //None once the session is over or for a guess already tried
+    pub fn guess(&mut self, guess: Guess) -> Option<Feedback> {
+        if !self.can_guess(&guess) {
+            return None;
+        }
+        let correct = self.challenge.check(&guess);
+        let mistakes = if correct {
+            vec![]
+        } else {
+            self.challenge.diagnose(&guess)
+        };
+        self.guesses.push(guess);
+        let tip = if correct {
+            self.finish(Outcome::Found);
+            None
+        } else {
+            self.next_tip()
+        };
+        Some(Feedback {
+            correct,
+            tries: self.tries(),
+            tip,
+            mistakes,
+        })
+    }
+
+    pub fn hint(&mut self) -> Option<String> {
+        if self.end.is_some() {
+            return None;
+        }
+        self.hints += 1;
+        self.next_tip()
+    }
+
+    pub fn skip(&mut self) {
+        if self.end.is_none() {
+            self.finish(Outcome::Skipped);
+        }
+    }
+
+    pub fn reveal(&mut self) -> &Tips {
+        self.give_up();
+        &self.challenge.tips
+    }
+
+    //a session left before its end counts as given up
+    pub fn give_up(&mut self) {
+        if self.end.is_none() {
+            self.finish(Outcome::GaveUp);
+        }
+    }
+
+    //a guess or a hint was played
+    pub fn is_started(&self) -> bool {
+        !self.guesses.is_empty() || self.hints > 0
+    }
+
+    //a challenge shown after it was drawn is timed from then, a played one keeps its time
+    pub fn restart_clock(&mut self) {
+        if !self.is_started() && self.end.is_none() {
+            self.start = Instant::now();
+        }
+    }
+
+    //the countdown ran out before the answer was found
+    pub fn time_out(&mut self) {
+        if self.end.is_none() {
+            self.finish(Outcome::TimedOut);
+        }
+    }
+
+    //a guess already tried or any guess once the session is over can't be played
+    pub fn can_guess(&self, guess: &Guess) -> bool {
+        self.end.is_none() && !self.guesses.contains(guess)
+    }
+
+    //in the order they were played
+    pub fn guesses(&self) -> &[Guess] {
+        &self.guesses
+    }
+
+    pub fn tries(&self) -> u32 {
+        self.guesses.len() as u32
+    }
+
+    pub fn hints(&self) -> u32 {
+        self.hints
+    }
+
+    pub fn outcome(&self) -> Option<Outcome> {
+        self.end.map(|(outcome, _)| outcome)
+    }
+
+    //stops with the session
+    pub fn elapsed(&self) -> Duration {
+        match self.end {
+            Some((_, time)) => time,
+            None => self.start.elapsed(),
+        }
+    }
+
+    pub fn found_at_first_guess(&self) -> bool {
+        self.outcome() == Some(Outcome::Found) && self.tries() == 1
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Score {
+    pub rounds: usize,
+    pub first_guess: usize,
+    pub tries: u32,
+    pub hints: u32,
+    pub accuracy: f64,
+    pub average_time: Duration,
+}
+
+pub fn score(sessions: &[TrainingSession]) -> Score {
+    let rounds = sessions.len();
+    let first_guess = sessions
+        .iter()
+        .filter(|session| session.found_at_first_guess())
+        .count();
+    let time: Duration = sessions.iter().map(TrainingSession::elapsed).sum();
+    Score {
+        rounds,
+        first_guess,
+        tries: sessions.iter().map(TrainingSession::tries).sum(),
+        hints: sessions.iter().map(TrainingSession::hints).sum(),
+        accuracy: first_guess as f64 / rounds.max(1) as f64,
+        average_time: time / rounds.max(1) as u32,
+    }
+}
+
+#[test]
+fn challenge_parse_check() {
+    let dt = NaiveDate::from_ymd_opt(1980, 2, 1).unwrap();
+    let day = Challenge::day(Method::ShakuntalaDevi, dt);
+    assert_eq!(day.parse("5"), Some(Guess::Weekday(Weekday::Fri)));
+    assert_eq!(day.parse("Monday"), None);
+    assert_eq!(
+        day.parse_with("5", Numbering::Iso),
+        Some(Guess::Weekday(Weekday::Fri))
+    );
+    assert_eq!(day.parse_with("0", Numbering::Iso), None);
+    assert!(day.check(&day.parse("5").unwrap()));
+    assert!(!day.check(&Guess::Entry(5)));
+    let table = Challenge::table(TableDrill::Month, dt);
+    assert_eq!(table.parse("3"), Some(Guess::Entry(3)));
+    assert_eq!(table.parse("7"), None);
+    assert!(table.check(&Guess::Entry(3)));
+    let days = Challenge::days_between(dt, NaiveDate::from_ymd_opt(1980, 3, 1).unwrap());
+    assert!(days.check(&days.parse("29").unwrap()));
+    let puzzle = Challenge::puzzle(Puzzle::MonthsStartingOn {
+        year: 2026,
+        weekday: Weekday::Sun,
+    });
+    assert_eq!(puzzle.parse(""), None);
+    assert!(puzzle.check(&puzzle.parse("2, 3, 11").unwrap()));
+}
+
+#[test]
+fn training_session_check() {
+    let dt = NaiveDate::from_ymd_opt(1980, 2, 1).unwrap();
+    let challenge = Challenge::day(Method::ShakuntalaDevi, dt);
+    let tips = challenge.tips.0.clone();
+    let mut session = TrainingSession::new(challenge);
+    //hints and wrong guesses share the same tips
+    assert_eq!(session.hint(), tips.front().cloned());
+    let feedback = session.guess(Guess::Weekday(Weekday::Mon)).unwrap();
+    assert!(!feedback.correct);
+    assert_eq!(feedback.tries, 1);
+    assert_eq!(feedback.tip, tips.get(1).cloned());
+    assert_eq!(feedback.mistakes, vec![]);
+    assert!(!session.can_guess(&Guess::Weekday(Weekday::Mon)));
+    //a guess already tried is not counted again
+    assert_eq!(session.guess(Guess::Weekday(Weekday::Mon)), None);
+    assert_eq!(session.tries(), 1);
+    assert_eq!(
+        session.guess(Guess::Weekday(Weekday::Tue)).unwrap().tip,
+        None
+    );
+    assert_eq!(session.outcome(), None);
+    let feedback = session.guess(Guess::Weekday(Weekday::Fri)).unwrap();
+    assert!(feedback.correct);
+    assert_eq!(feedback.tries, 3);
+    assert_eq!(session.outcome(), Some(Outcome::Found));
+    assert_eq!(session.hints(), 1);
+    assert!(!session.found_at_first_guess());
+    //nothing changes once the session is over
+    assert_eq!(session.guess(Guess::Weekday(Weekday::Fri)), None);
+    assert_eq!(session.hint(), None);
+    assert!(!session.can_guess(&Guess::Weekday(Weekday::Sat)));
+    assert_eq!(session.elapsed(), session.elapsed());
+}
+
+#[test]
+fn give_up_check() {
+    let dt = NaiveDate::from_ymd_opt(1980, 2, 1).unwrap();
+    let mut session = TrainingSession::new(Challenge::day(Method::ShakuntalaDevi, dt));
+    assert!(!session.is_started());
+    session.guess(Guess::Weekday(Weekday::Mon));
+    assert!(session.is_started());
+    session.give_up();
+    assert_eq!(session.outcome(), Some(Outcome::GaveUp));
+    let mut found = TrainingSession::new(Challenge::day(Method::ShakuntalaDevi, dt));
+    found.guess(Guess::Weekday(Weekday::Fri));
+    found.give_up();
+    assert_eq!(found.outcome(), Some(Outcome::Found));
+}
+
+#[test]
+fn restart_clock_check() {
+    let dt = NaiveDate::from_ymd_opt(1980, 2, 1).unwrap();
+    let mut session = TrainingSession::new(Challenge::day(Method::ShakuntalaDevi, dt));
+    std::thread::sleep(Duration::from_millis(50));
+    session.restart_clock();
+    assert!(session.elapsed() < Duration::from_millis(50));
+    session.hint();
+    std::thread::sleep(Duration::from_millis(50));
+    session.restart_clock();
+    assert!(session.elapsed() >= Duration::from_millis(50));
+}
+
+#[test]
+fn table_tip_check() {
+    let dt = NaiveDate::from_ymd_opt(1990, 5, 1).unwrap();
+    let mut session = TrainingSession::new(Challenge::table(TableDrill::Year, dt));
+    let tip = Some("no direct year table entry, nearest leap year 1988".to_string());
+    let wrong = (TableDrill::Year.answer(dt) + 1) % 7;
+    assert_eq!(session.guess(Guess::Entry(wrong)).unwrap().tip, tip);
+    assert_eq!(
+        session.guess(Guess::Entry((wrong + 1) % 7)).unwrap().tip,
+        tip
+    );
+    assert_eq!(session.hint(), tip);
+}
+
+#[test]
+fn score_check() {
+    let dt = NaiveDate::from_ymd_opt(1980, 2, 1).unwrap();
+    let mut found = TrainingSession::new(Challenge::table(TableDrill::Month, dt));
+    found.guess(Guess::Entry(3));
+    let mut skipped = TrainingSession::new(Challenge::table(TableDrill::Month, dt));
+    skipped.hint();
+    skipped.skip();
+    let mut gave_up = TrainingSession::new(Challenge::table(TableDrill::Month, dt));
+    gave_up.guess(Guess::Entry(0));
+    assert_eq!(gave_up.reveal().0.len(), 0);
+    assert_eq!(skipped.outcome(), Some(Outcome::Skipped));
+    assert_eq!(gave_up.outcome(), Some(Outcome::GaveUp));
+    let mut timed_out = TrainingSession::new(Challenge::table(TableDrill::Month, dt));
+    timed_out.time_out();
+    timed_out.reveal();
+    assert_eq!(timed_out.outcome(), Some(Outcome::TimedOut));
+    assert!(!timed_out.can_guess(&Guess::Entry(3)));
+    let total = score(&[found, skipped, gave_up]);
+    assert_eq!(total.rounds, 3);
+    assert_eq!(total.first_guess, 1);
+    assert_eq!(total.tries, 2);
+    assert_eq!(total.hints, 1);
+    assert!((total.accuracy - 1.0 / 3.0).abs() < f64::EPSILON);
+    assert_eq!(score(&[]).rounds, 0);
+}
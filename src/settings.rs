@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+use shakuntala_devi_trainer::{Method, Numbering};
+
+use crate::gui::common::Screen;
+use crate::storage::{self, Place};
+
+const FILE_NAME: &str = "settings.toml";
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    #[default]
+    Light,
+    Dark,
+    HighContrast,
+    ColorBlind,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 4] = [
+        Theme::Light,
+        Theme::Dark,
+        Theme::HighContrast,
+        Theme::ColorBlind,
+    ];
+
+    pub fn next(self) -> Theme {
+        let index = Theme::ALL.iter().position(|&theme| theme == self).unwrap();
+        Theme::ALL[(index + 1) % Theme::ALL.len()]
+    }
+}
+
+//a missing field takes its default value so old files keep working
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct Settings {
+    pub first_year: u32,
+    pub last_year: u32,
+    pub method: Method,
+    pub numbering: Numbering,
+    //only English for now
+    pub language: String,
+    pub theme: Theme,
+    pub show_timer: bool,
+    pub screen: Screen,
+    //the GUI day game follows the adaptive level instead of the sliders
+    pub adaptive: bool,
+    //seconds to find the day of the GUI game, 0 for no countdown
+    pub countdown: u64,
+    //the first year of the lifetime preset of the GUI
+    pub birth_year: Option<u32>,
+    //the year table drill of the GUI has its own range
+    pub year_table_first_year: u32,
+    pub year_table_last_year: u32,
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings {
+            first_year: shakuntala_devi_trainer::DEFAULT_FIRST_YEAR,
+            last_year: shakuntala_devi_trainer::DEFAULT_LAST_YEAR,
+            method: Method::default(),
+            numbering: Numbering::default(),
+            language: "en".to_string(),
+            theme: Theme::default(),
+            show_timer: true,
+            screen: Screen::default(),
+            adaptive: false,
+            countdown: 0,
+            birth_year: None,
+            year_table_first_year: shakuntala_devi_trainer::DEFAULT_FIRST_YEAR,
+            year_table_last_year: shakuntala_devi_trainer::DEFAULT_LAST_YEAR,
+        }
+    }
+}
+
+impl Settings {
+    //the default settings when there is no file yet or it can't be read
+    pub fn load() -> Settings {
+        let mut settings = match storage::read(Place::Config, FILE_NAME) {
+            Some(text) => toml::from_str(&text).unwrap_or_else(|error| {
+                eprintln!("Ignoring the settings: {}", error);
+                Settings::default()
+            }),
+            None => Settings::default(),
+        };
+        //a wrong range is replaced with the default one so the settings can't lock the user out
+        if let Err(error) = shakuntala_devi_trainer::validate_year_range(
+            settings.first_year,
+            settings.last_year,
+            settings.method,
+        ) {
+            eprintln!("Ignoring the year range of the settings: {}", error);
+            settings.first_year = shakuntala_devi_trainer::DEFAULT_FIRST_YEAR;
+            settings.last_year = shakuntala_devi_trainer::DEFAULT_LAST_YEAR;
+        }
+        //the year table drill draws its cards in this range, the default one replaces a wrong one
+        if let Err(error) = shakuntala_devi_trainer::validate_year_range(
+            settings.year_table_first_year,
+            settings.year_table_last_year,
+            Method::ShakuntalaDevi,
+        ) {
+            eprintln!("Ignoring the year table range of the settings: {}", error);
+            settings.year_table_first_year = shakuntala_devi_trainer::DEFAULT_FIRST_YEAR;
+            settings.year_table_last_year = shakuntala_devi_trainer::DEFAULT_LAST_YEAR;
+        }
+        settings
+    }
+
+    pub fn save(&self) {
+        if let Err(error) =
+            storage::write(Place::Config, FILE_NAME, &toml::to_string(self).unwrap())
+        {
+            eprintln!("Cannot save the settings: {}", error);
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use chrono::prelude::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::history::Attempt;
+use crate::session::Mode;
+use crate::weekday_name;
+
+//accuracy is the share found at the first guess like in the play summary
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Line {
+    pub label: String,
+    pub attempts: usize,
+    pub accuracy: f64,
+    //of the attempts found, in seconds
+    pub median_time: Option<f64>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Breakdown {
+    pub by: &'static str,
+    pub lines: Vec<Line>,
+}
+
+fn median(mut times: Vec<f64>) -> Option<f64> {
+    times.sort_by(f64::total_cmp);
+    let middle = times.len() / 2;
+    match times.len() {
+        0 => None,
+        n if n % 2 == 0 => Some((times[middle - 1] + times[middle]) / 2.0),
+        _ => Some(times[middle]),
+    }
+}
+
+fn line(label: String, attempts: &[&Attempt]) -> Line {
+    let first_guess = attempts
+        .iter()
+        .filter(|attempt| attempt.found_at_first_guess())
+        .count();
+    Line {
+        label,
+        attempts: attempts.len(),
+        accuracy: first_guess as f64 / attempts.len().max(1) as f64,
+        median_time: median(
+            attempts
+                .iter()
+                .filter(|attempt| attempt.success())
+                .map(|attempt| attempt.time)
+                .collect(),
+        ),
+    }
+}
+
+//the lines come in the order of their keys, attempts without a key are left out
+fn breakdown<K: Ord>(
+    by: &'static str,
+    attempts: &[Attempt],
+    key: impl Fn(&Attempt) -> Option<K>,
+    label: impl Fn(K) -> String,
+) -> Breakdown {
+    let mut groups: BTreeMap<K, Vec<&Attempt>> = BTreeMap::new();
+    for attempt in attempts {
+        if let Some(key) = key(attempt) {
+            groups.entry(key).or_default().push(attempt);
+        }
+    }
+    Breakdown {
+        by,
+        lines: groups
+            .into_iter()
+            .map(|(key, group)| line(label(key), &group))
+            .collect(),
+    }
+}
+
+//the weekday of a date is what these modes ask, the rushed blitz guesses only count per mode
+fn asks_weekday_of_date(mode: Mode) -> bool {
+    matches!(mode, Mode::Day | Mode::Guided)
+}
+
+//a breakdown only counts the attempts whose challenge asked about it, the table drills
+//store a date that only carries their month, year or century
+fn asked_month(attempt: &Attempt) -> Option<u32> {
+    match attempt.mode {
+        mode if asks_weekday_of_date(mode) => attempt.date.map(|dt| dt.month()),
+        Mode::MonthTable => attempt.date.map(|dt| dt.month()),
+        _ => None,
+    }
+}
+
+fn asked_century(attempt: &Attempt) -> Option<i32> {
+    match attempt.mode {
+        mode if asks_weekday_of_date(mode) => attempt.date.map(|dt| dt.year() / 100),
+        Mode::YearTable | Mode::CenturyTable => attempt.date.map(|dt| dt.year() / 100),
+        _ => None,
+    }
+}
+
+fn asked_leap_year(attempt: &Attempt) -> Option<bool> {
+    match attempt.mode {
+        mode if asks_weekday_of_date(mode) => attempt.date.map(|dt| dt.leap_year()),
+        _ => None,
+    }
+}
+
+//the weekday drill asks the weekday of its answer
+fn asked_weekday(attempt: &Attempt) -> Option<Weekday> {
+    match attempt.mode {
+        mode if asks_weekday_of_date(mode) => attempt.date.map(|dt| dt.weekday()),
+        Mode::WeekdayDrill => attempt.answer.parse().ok(),
+        _ => None,
+    }
+}
+
+//the weeks show the trend
+pub fn breakdowns(attempts: &[Attempt]) -> Vec<Breakdown> {
+    vec![
+        breakdown(
+            "mode",
+            attempts,
+            |attempt| Some(attempt.mode),
+            //the name of the history, the blitz has no command line name
+            |mode| {
+                serde_json::to_value(mode)
+                    .unwrap()
+                    .as_str()
+                    .unwrap()
+                    .to_string()
+            },
+        ),
+        breakdown("month", attempts, asked_month, |month| {
+            Month::try_from(month as u8).unwrap().name().to_string()
+        }),
+        breakdown("century", attempts, asked_century, |century| {
+            format!("{}-{}", century * 100, century * 100 + 99)
+        }),
+        breakdown("leap_year", attempts, asked_leap_year, |leap| {
+            if leap { "leap" } else { "common" }.to_string()
+        }),
+        breakdown(
+            "weekday",
+            attempts,
+            |attempt| asked_weekday(attempt).map(|weekday| weekday.num_days_from_monday()),
+            |weekday| weekday_name(Weekday::try_from(weekday as u8).unwrap()).to_string(),
+        ),
+        breakdown(
+            "week",
+            attempts,
+            |attempt| {
+                let week = attempt.played_at.iso_week();
+                Some((week.year(), week.week()))
+            },
+            |(year, week)| format!("{}-W{:02}", year, week),
+        ),
+    ]
+}
+
+#[test]
+fn breakdowns_check() {
+    use crate::session::Outcome;
+    use crate::Method;
+    let attempt = |date: &str, guesses: &[&str], time, result| Attempt {
+        played_at: "2026-10-19T10:00:00Z".parse().unwrap(),
+        mode: Mode::Day,
+        method: Method::ShakuntalaDevi,
+        date: Some(date.parse().unwrap()),
+        challenge: date.to_string(),
+        answer: "".to_string(),
+        guesses: guesses.iter().map(|guess| guess.to_string()).collect(),
+        hints: 0,
+        time,
+        result,
+    };
+    let attempts = [
+        attempt("1980-02-01", &["Fri"], 3.0, Outcome::Found),
+        attempt("1981-02-01", &["Mon", "Sun"], 5.0, Outcome::Found),
+        attempt("2000-03-01", &[], 1.0, Outcome::Skipped),
+        attempt("2004-03-02", &["Tue"], 2.0, Outcome::Found),
+    ];
+    let breakdowns = breakdowns(&attempts);
+    let by = |by| {
+        &breakdowns
+            .iter()
+            .find(|breakdown| breakdown.by == by)
+            .unwrap()
+            .lines
+    };
+    assert_eq!(
+        by("mode"),
+        &vec![Line {
+            label: "day".to_string(),
+            attempts: 4,
+            accuracy: 0.5,
+            median_time: Some(3.0),
+        }]
+    );
+    let month = by("month");
+    assert_eq!(month[0].label, "February");
+    assert_eq!(month[0].accuracy, 0.5);
+    assert_eq!(month[0].median_time, Some(4.0));
+    assert_eq!(month[1].label, "March");
+    assert_eq!(month[1].median_time, Some(2.0));
+    let century = by("century");
+    assert_eq!(century[0].label, "1900-1999");
+    assert_eq!(century[1].label, "2000-2099");
+    let leap = by("leap_year");
+    assert_eq!((leap[0].label.as_str(), leap[0].attempts), ("common", 1));
+    assert_eq!((leap[1].label.as_str(), leap[1].attempts), ("leap", 3));
+    assert_eq!(by("weekday")[0].label, "Tuesday");
+    assert_eq!(by("week")[0].label, "2026-W43");
+}
+
+#[test]
+fn breakdowns_mixed_modes_check() {
+    use crate::session::Outcome;
+    use crate::Method;
+    let attempt = |mode, date: &str, answer: &str| Attempt {
+        played_at: "2026-10-19T10:00:00Z".parse().unwrap(),
+        mode,
+        method: Method::ShakuntalaDevi,
+        date: Some(date.parse().unwrap()),
+        challenge: date.to_string(),
+        answer: answer.to_string(),
+        guesses: vec![],
+        hints: 0,
+        time: 1.0,
+        result: Outcome::GaveUp,
+    };
+    let attempts = [
+        attempt(Mode::Day, "1980-02-01", "Friday"),
+        attempt(Mode::MonthTable, "1905-08-01", "2"),
+        attempt(Mode::YearTable, "2004-01-01", "1"),
+        attempt(Mode::CenturyTable, "1700-01-01", "4"),
+        attempt(Mode::WeekdayDrill, "1999-12-31", "Monday"),
+        attempt(Mode::DaysBetween, "1950-06-01", "10"),
+        attempt(Mode::Blitz, "1600-03-01", "Wednesday"),
+    ];
+    let breakdowns = breakdowns(&attempts);
+    let labels = |by| -> Vec<(String, usize)> {
+        breakdowns
+            .iter()
+            .find(|breakdown| breakdown.by == by)
+            .unwrap()
+            .lines
+            .iter()
+            .map(|line| (line.label.clone(), line.attempts))
+            .collect()
+    };
+    let line = |label: &str, attempts| (label.to_string(), attempts);
+    assert_eq!(labels("mode").len(), 7);
+    assert!(labels("mode").contains(&line("blitz", 1)));
+    assert_eq!(
+        labels("month"),
+        vec![line("February", 1), line("August", 1)]
+    );
+    assert_eq!(
+        labels("century"),
+        vec![
+            line("1700-1799", 1),
+            line("1900-1999", 1),
+            line("2000-2099", 1)
+        ]
+    );
+    assert_eq!(labels("leap_year"), vec![line("leap", 1)]);
+    assert_eq!(
+        labels("weekday"),
+        vec![line("Monday", 1), line("Friday", 1)]
+    );
+}
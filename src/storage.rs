@@ -0,0 +1,73 @@
+//files of the config and data dirs, or the browser localStorage for the web version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Place {
+    Config,
+    Data,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn path(place: Place, name: &str) -> Option<std::path::PathBuf> {
+    let dir = match place {
+        Place::Config => dirs::config_dir()?,
+        Place::Data => dirs::data_dir()?,
+    };
+    Some(dir.join(env!("CARGO_PKG_NAME")).join(name))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn create(place: Place, name: &str) -> Result<std::path::PathBuf, String> {
+    let path = path(place, name).ok_or("no home directory")?;
+    std::fs::create_dir_all(path.parent().unwrap()).map_err(|error| error.to_string())?;
+    Ok(path)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn read(place: Place, name: &str) -> Option<String> {
+    std::fs::read_to_string(path(place, name)?).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write(place: Place, name: &str, text: &str) -> Result<(), String> {
+    std::fs::write(create(place, name)?, text).map_err(|error| error.to_string())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn append(place: Place, name: &str, text: &str) -> Result<(), String> {
+    use std::io::Write;
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(create(place, name)?)
+        .and_then(|mut file| file.write_all(text.as_bytes()))
+        .map_err(|error| error.to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+//a single browser storage, the place only matters for the files
+#[cfg(target_arch = "wasm32")]
+fn key(name: &str) -> String {
+    format!("{}/{}", env!("CARGO_PKG_NAME"), name)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn read(_place: Place, name: &str) -> Option<String> {
+    storage()?.get_item(&key(name)).ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn write(_place: Place, name: &str, text: &str) -> Result<(), String> {
+    storage()
+        .ok_or("no localStorage")?
+        .set_item(&key(name), text)
+        .map_err(|error| format!("{:?}", error))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn append(place: Place, name: &str, text: &str) -> Result<(), String> {
+    let previous = read(place, name).unwrap_or_default();
+    write(place, name, &(previous + text))
+}
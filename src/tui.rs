@@ -0,0 +1,373 @@
+use chrono::prelude::*;
+use enum_map::{Enum, EnumMap};
+use num_traits::cast::FromPrimitive;
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Tabs, Wrap},
+    DefaultTerminal, Frame,
+};
+use shakuntala_devi_trainer::diagnosis::possible_mistakes;
+use shakuntala_devi_trainer::session::{Challenge, Guess, TrainingSession};
+use shakuntala_devi_trainer::{Method, Numbering, TableDrill};
+use std::io;
+use std::time::Duration;
+
+use crate::practice;
+use crate::settings::Settings;
+
+//the four screens of the GUI, in the same order as its menu
+#[derive(Debug, Enum, Clone, Copy, PartialEq, Eq)]
+enum Screen {
+    Game,
+    Solution,
+    MonthTable,
+    YearTable,
+}
+
+const SCREENS: [Screen; 4] = [
+    Screen::Game,
+    Screen::Solution,
+    Screen::MonthTable,
+    Screen::YearTable,
+];
+
+const WEEKDAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+const ENTRIES: [&str; 7] = ["0", "1", "2", "3", "4", "5", "6"];
+
+impl Screen {
+    fn title(self) -> &'static str {
+        match self {
+            Screen::Game => "DAY TRAINING MODE",
+            Screen::Solution => "SOLUTION",
+            Screen::MonthTable => "MONTH TABLE",
+            Screen::YearTable => "YEAR TABLE",
+        }
+    }
+}
+
+fn initial_hint() -> EnumMap<Screen, String> {
+    enum_map! {
+        Screen::Game => "Guess the day!".to_string(),
+        Screen::Solution => "".to_string(),
+        Screen::MonthTable => "Which entry is the good one ?".to_string(),
+        Screen::YearTable => "Which entry is the good one ?".to_string(),
+    }
+}
+
+struct Tui {
+    screen: Screen,
+    settings: Settings,
+    random_date: NaiveDate,
+    game: TrainingSession,
+    month_table: TrainingSession,
+    year_table: TrainingSession,
+    selected: usize,
+    hint: EnumMap<Screen, String>,
+    quit: bool,
+}
+
+pub fn run_tui(settings: Settings) -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    let result = Tui::new(settings).run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+impl Tui {
+    fn new(settings: Settings) -> Tui {
+        let random_date =
+            shakuntala_devi_trainer::random_date(settings.first_year, settings.last_year);
+        //the table drills ask the cards chosen by the spaced repetition
+        let month_table = TrainingSession::new(practice::table_challenge(
+            TableDrill::Month,
+            settings.first_year,
+            settings.last_year,
+        ));
+        let year_table = TrainingSession::new(practice::table_challenge(
+            TableDrill::Year,
+            settings.first_year,
+            settings.last_year,
+        ));
+        let mut hint = initial_hint();
+        hint[Screen::MonthTable] = month_table.challenge.question.clone();
+        hint[Screen::YearTable] = year_table.challenge.question.clone();
+        Tui {
+            screen: Screen::Game,
+            settings,
+            random_date,
+            game: TrainingSession::new(Challenge::day(Method::ShakuntalaDevi, random_date)),
+            month_table,
+            year_table,
+            selected: 0,
+            hint,
+            quit: false,
+        }
+    }
+
+    //poll with a timeout so the timer keeps ticking without any key press
+    fn run(mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if event::poll(Duration::from_millis(200))? {
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press {
+                        self.on_key(key.code);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    //the challenges tried and left are given up
+    fn give_up_started(&mut self) {
+        for session in [&mut self.game, &mut self.month_table, &mut self.year_table] {
+            if session.is_started() {
+                practice::give_up(session);
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.give_up_started();
+        *self = Tui {
+            screen: self.screen,
+            selected: self.selected,
+            ..Tui::new(self.settings.clone())
+        };
+    }
+
+    fn switch(&mut self, step: usize) {
+        let index = SCREENS.iter().position(|&s| s == self.screen).unwrap();
+        self.screen = SCREENS[(index + step) % SCREENS.len()];
+    }
+
+    fn on_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => {
+                self.give_up_started();
+                self.quit = true
+            }
+            KeyCode::Char('n') => self.reset(),
+            KeyCode::Tab => self.switch(1),
+            KeyCode::BackTab => self.switch(SCREENS.len() - 1),
+            KeyCode::F(n @ 1..=4) => self.screen = SCREENS[n as usize - 1],
+            KeyCode::Left => self.selected = (self.selected + 6) % 7,
+            KeyCode::Right => self.selected = (self.selected + 1) % 7,
+            KeyCode::Enter | KeyCode::Char(' ') => self.press(self.selected),
+            //weekdays are typed with the numbering of the settings
+            KeyCode::Char(c @ '0'..='7') => {
+                let index = match self.screen {
+                    Screen::Game => self
+                        .settings
+                        .numbering
+                        .parse(&c.to_string())
+                        .map(|weekday| weekday.num_days_from_monday()),
+                    _ => c.to_digit(10).filter(|&n| n < 7),
+                };
+                if let Some(index) = index {
+                    self.selected = index as usize;
+                    self.press(index as usize);
+                }
+            }
+            KeyCode::Char('[') => self.change_range(-1, 0),
+            KeyCode::Char(']') => self.change_range(1, 0),
+            KeyCode::Char('{') => self.change_range(0, -1),
+            KeyCode::Char('}') => self.change_range(0, 1),
+            _ => (),
+        }
+    }
+
+    //like the GUI sliders the new range is used from the next game
+    fn change_range(&mut self, first_step: i32, last_step: i32) {
+        let first_year = self.settings.first_year.saturating_add_signed(first_step);
+        let last_year = self.settings.last_year.saturating_add_signed(last_step);
+        match shakuntala_devi_trainer::validate_year_range(
+            first_year,
+            last_year,
+            Method::ShakuntalaDevi,
+        ) {
+            Ok(()) => {
+                self.settings.first_year = first_year;
+                self.settings.last_year = last_year;
+            }
+            Err(error) => self.hint[self.screen] = error.to_string(),
+        }
+    }
+
+    fn session(&self, screen: Screen) -> Option<&TrainingSession> {
+        match screen {
+            Screen::Game => Some(&self.game),
+            Screen::Solution => None,
+            Screen::MonthTable => Some(&self.month_table),
+            Screen::YearTable => Some(&self.year_table),
+        }
+    }
+
+    //the buttons go from Monday to Sunday on the game screen and from 0 to 6 on the tables
+    fn button_guess(&self, index: usize) -> Guess {
+        match self.screen {
+            Screen::Game => Guess::Weekday(Weekday::from_usize(index).unwrap()),
+            _ => Guess::Entry(index as i32),
+        }
+    }
+
+    fn press(&mut self, index: usize) {
+        let guess = self.button_guess(index);
+        let session = match self.screen {
+            Screen::Game => &mut self.game,
+            Screen::Solution => return,
+            Screen::MonthTable => &mut self.month_table,
+            Screen::YearTable => &mut self.year_table,
+        };
+        if !session.can_guess(&guess) {
+            return;
+        }
+        let feedback = session.guess(guess.clone()).unwrap();
+        if feedback.correct {
+            practice::record(session);
+        }
+        self.hint[self.screen] = match (self.screen, feedback.correct, feedback.tip) {
+            (Screen::Game, true, _) if self.settings.show_timer => format!(
+                "Congratulation ! You found {} after {} guess in {:#?}s",
+                guess,
+                feedback.tries,
+                session.elapsed().as_secs()
+            ),
+            (Screen::Game, true, _) => format!(
+                "Congratulation ! You found {} after {} guess",
+                guess, feedback.tries
+            ),
+            (Screen::Game, false, tip) => {
+                let tip = match tip {
+                    Some(tips) => format!("tips: {:#?}", tips),
+                    None => "Sorry, no more tips".to_string(),
+                };
+                match possible_mistakes(&feedback.mistakes) {
+                    Some(mistakes) => format!("{}\n{}", mistakes, tip),
+                    None => tip,
+                }
+            }
+            (_, true, _) => format!("Congratulation ! {} is the right answer", guess),
+            (_, false, Some(tips)) => format!("Try again. Tips: {}", tips),
+            (_, false, None) => "Try again".to_string(),
+        };
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [menu, status, challenge, buttons, hint, help] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Min(3),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let title = format!(" Shakuntala Devi trainer {} ", env!("CARGO_PKG_VERSION"));
+        frame.render_widget(
+            Tabs::new(SCREENS.map(Screen::title))
+                .select(SCREENS.iter().position(|&s| s == self.screen))
+                .highlight_style(Style::new().bold().reversed())
+                .block(Block::bordered().title(title)),
+            menu,
+        );
+
+        let mut years = format!(
+            "Years {} to {}",
+            self.settings.first_year, self.settings.last_year
+        );
+        if self.settings.show_timer {
+            years += &format!("    Time {}s", self.game.elapsed().as_secs());
+        }
+        frame.render_widget(Paragraph::new(years).centered(), status);
+
+        let month = Month::from_u32(self.random_date.month()).unwrap().name();
+        let date = format!(
+            "{} {} {}",
+            self.random_date.day(),
+            month,
+            self.random_date.year()
+        );
+        let question = match self.screen {
+            Screen::Game | Screen::Solution => date,
+            Screen::MonthTable => self.month_table.challenge.challenge.clone(),
+            Screen::YearTable => self.year_table.challenge.challenge.clone(),
+        };
+        frame.render_widget(
+            Paragraph::new(question)
+                .bold()
+                .centered()
+                .block(Block::bordered()),
+            challenge,
+        );
+
+        let labels = match self.screen {
+            Screen::Game => WEEKDAYS,
+            _ => ENTRIES,
+        };
+        if self.screen != Screen::Solution {
+            let spans: Vec<Span> = labels
+                .iter()
+                .enumerate()
+                .map(|(index, label)| {
+                    let span = Span::raw(format!(" {} ", label));
+                    let span = if self
+                        .session(self.screen)
+                        .is_some_and(|session| !session.can_guess(&self.button_guess(index)))
+                    {
+                        span.dark_gray().crossed_out()
+                    } else {
+                        span
+                    };
+                    if index == self.selected {
+                        span.reversed()
+                    } else {
+                        span
+                    }
+                })
+                .collect();
+            frame.render_widget(
+                Paragraph::new(Line::from(spans).centered()).block(Block::bordered()),
+                buttons,
+            );
+        }
+
+        let text = match self.screen {
+            Screen::Solution => self.game.challenge.tips.to_string(),
+            _ => self.hint[self.screen].clone(),
+        };
+        frame.render_widget(
+            Paragraph::new(text)
+                .wrap(Wrap { trim: false })
+                .block(Block::bordered()),
+            hint,
+        );
+
+        frame.render_widget(
+            Paragraph::new(format!(
+                "Tab/F1-F4 screen  Left/Right Enter choose  {} answer  n new game  [ ] first year  {{ }} last year  q quit",
+                match (self.screen, self.settings.numbering) {
+                    (Screen::Game, Numbering::Iso) => "1-7",
+                    _ => "0-6",
+                }
+            ))
+            .dark_gray()
+            .centered(),
+            help,
+        );
+    }
+}